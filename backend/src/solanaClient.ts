@@ -124,6 +124,8 @@ export interface PokemonSlotData {
   posY: number;
   throwAttempts: number;
   spawnTimestamp: BN;
+  /** 0 = common, 1 = uncommon, 2 = rare, 3 = legendary */
+  rarity: number;
 }
 
 export interface PokemonSlotsAccount {
@@ -233,15 +235,16 @@ export class SolanaClient {
 
   /**
    * Force-spawn a Pokemon at specific coordinates (authority only).
-   * Calls the on-chain force_spawn_pokemon instruction.
+   * Calls the on-chain force_spawn_pokemon instruction. Rarity defaults to common.
    */
   async forceSpawnPokemon(
     slotIndex: number,
     posX: number,
-    posY: number
+    posY: number,
+    rarity: number = 0
   ): Promise<string> {
     const tx = await this.program.methods
-      .forceSpawnPokemon(slotIndex, posX, posY)
+      .forceSpawnPokemon(slotIndex, posX, posY, rarity)
       .accounts({
         authority: this.wallet.publicKey,
         gameConfig: this.pdas.gameConfig,
@@ -308,14 +311,17 @@ export class SolanaClient {
   }
 
   /**
-   * Withdraw SolBalls revenue from the game account to the backend wallet.
+   * Withdraw SolBalls revenue from the game account to the treasury wallet.
+   * The program only pays out to TreasuryConfig.treasury_wallet, so the
+   * swap pipeline requires the backend wallet to be the configured treasury.
    * Returns the transaction signature.
    */
   async withdrawRevenue(amount: bigint): Promise<string> {
     const gameSolballsAta = await this.getGameSolballsAta();
-    const backendSolballsAta = await getAssociatedTokenAddress(
+    const { treasuryWallet } = await this.getTreasuryConfig();
+    const treasurySolballsAta = await getAssociatedTokenAddress(
       SOLBALLS_MINT,
-      this.wallet.publicKey
+      treasuryWallet
     );
 
     const tx = await this.program.methods
@@ -325,7 +331,7 @@ export class SolanaClient {
         gameConfig: this.pdas.gameConfig,
         treasuryConfig: this.pdas.treasuryConfig,
         gameSolballsAccount: gameSolballsAta,
-        authoritySolballsAccount: backendSolballsAta,
+        treasurySolballsAccount: treasurySolballsAta,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
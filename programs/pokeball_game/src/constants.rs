@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 /// Maximum number of Pokemon that can be active at once (hard cap).
 pub const MAX_POKEMON_SLOTS: usize = 20;
 
@@ -11,18 +13,171 @@ pub const MAX_THROW_ATTEMPTS: u8 = 3;
 /// Matches ApeChain MAX_PURCHASE_USD = $49.90.
 pub const MAX_PURCHASE_AMOUNT: u64 = 49_900_000_000;
 
+/// Maximum USDC purchase amount per transaction in atomic units ($49.90, 6 decimals).
+pub const MAX_USDC_PURCHASE_AMOUNT: u64 = 49_900_000;
+
+/// Maximum SOL purchase amount per transaction in lamports (0.5 SOL).
+pub const MAX_SOL_PURCHASE_AMOUNT: u64 = 500_000_000;
+
+/// Payment currency: SolBalls.
+pub const CURRENCY_SOLBALLS: u8 = 0;
+
+/// Payment currency: USDC.
+pub const CURRENCY_USDC: u8 = 1;
+
+/// Payment currency: native SOL.
+pub const CURRENCY_SOL: u8 = 2;
+
+/// Price denomination: USD micro-units, paid in SolBalls at the oracle price.
+pub const CURRENCY_USD: u8 = 3;
+
+/// Maximum oracle-priced purchase per transaction in USD micro-units ($49.90).
+pub const MAX_PURCHASE_USD: u64 = 49_900_000;
+
+/// Default maximum age of an oracle price (seconds).
+pub const DEFAULT_MAX_PRICE_AGE: i64 = 60;
+
+/// Maximum configurable oracle price age (1 hour).
+pub const MAX_PRICE_AGE: i64 = 60 * 60;
+
+/// Default maximum oracle confidence interval (2% of price).
+pub const DEFAULT_MAX_PRICE_CONF_BPS: u16 = 200;
+
+/// Pyth oracle programs (mainnet-beta, devnet) allowed to own the price feed.
+pub const PYTH_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH"),
+    pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"),
+];
+
 /// Maximum number of NFTs the vault can hold.
 pub const MAX_VAULT_SIZE: u8 = 20;
 
 /// Number of ball types (Poke, Great, Ultra, Master).
 pub const NUM_BALL_TYPES: usize = 4;
 
+/// Ball token mints are whole balls (1 token = 1 ball).
+pub const BALL_TOKEN_DECIMALS: u8 = 0;
+
 /// VRF request type: spawn
 pub const VRF_TYPE_SPAWN: u8 = 0;
 
 /// VRF request type: throw
 pub const VRF_TYPE_THROW: u8 = 1;
 
+/// Pause flag: blocks purchase_balls and sell_back_balls.
+pub const PAUSE_PURCHASES: u8 = 1 << 0;
+
+/// Pause flag: blocks throw_ball and resolution of pending throws.
+pub const PAUSE_THROWS: u8 = 1 << 1;
+
+/// Pause flag: blocks spawn_pokemon, force_spawn_pokemon and resolution of pending spawns.
+pub const PAUSE_SPAWNS: u8 = 1 << 2;
+
+/// Pause flag: blocks NFT awards (claim_prize). Catches still record a PendingPrize.
+pub const PAUSE_NFT_AWARDS: u8 = 1 << 3;
+
+/// Pause flag: blocks transfer_balls between players.
+pub const PAUSE_TRANSFERS: u8 = 1 << 4;
+
+/// Pause flag: blocks ball tokens entering or leaving inventory (purchases into tokens,
+/// withdraw_ball_tokens and redeem_ball_tokens).
+pub const PAUSE_BALL_TOKENS: u8 = 1 << 5;
+
+/// Pause flag: blocks craft_balls.
+pub const PAUSE_CRAFTING: u8 = 1 << 6;
+
+/// All valid pause flags.
+pub const PAUSE_ALL: u8 = PAUSE_PURCHASES | PAUSE_THROWS | PAUSE_SPAWNS | PAUSE_NFT_AWARDS
+    | PAUSE_TRANSFERS | PAUSE_BALL_TOKENS | PAUSE_CRAFTING;
+
+/// Role: can change prices, catch rates and other game configuration.
+pub const ROLE_CONFIG_ADMIN: u8 = 0;
+
+/// Role: can spawn, reposition and despawn Pokemon.
+pub const ROLE_SPAWNER: u8 = 1;
+
+/// Role: can deposit and withdraw vault NFTs.
+pub const ROLE_VAULT_MANAGER: u8 = 2;
+
+/// Role: can withdraw revenue.
+pub const ROLE_TREASURER: u8 = 3;
+
+/// Number of roles.
+pub const NUM_ROLES: usize = 4;
+
+/// Default delay before a queued config change can be executed (24 hours).
+/// `localnet` builds start at 0 so the test suite can execute changes immediately.
+#[cfg(not(feature = "localnet"))]
+pub const DEFAULT_CONFIG_CHANGE_DELAY: i64 = 24 * 60 * 60;
+#[cfg(feature = "localnet")]
+pub const DEFAULT_CONFIG_CHANGE_DELAY: i64 = 0;
+
+/// Minimum configurable config change delay (1 hour; 0 on `localnet` builds).
+#[cfg(not(feature = "localnet"))]
+pub const MIN_CONFIG_CHANGE_DELAY: i64 = 60 * 60;
+#[cfg(feature = "localnet")]
+pub const MIN_CONFIG_CHANGE_DELAY: i64 = 0;
+
+/// Maximum configurable config change delay (30 days).
+pub const MAX_CONFIG_CHANGE_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Default seconds after which an unfulfilled VRF request can be cancelled.
+pub const DEFAULT_VRF_TIMEOUT: i64 = 5 * 60;
+
+/// Minimum configurable VRF timeout (ORAO normally fulfills in under a second).
+pub const MIN_VRF_TIMEOUT: i64 = 60;
+
+/// Maximum configurable VRF timeout (7 days).
+pub const MAX_VRF_TIMEOUT: i64 = 7 * 24 * 60 * 60;
+
+/// Revenue split bucket: treasury wallet.
+pub const SPLIT_TREASURY: usize = 0;
+
+/// Revenue split bucket: prize pool PDA (funds NFT purchases).
+pub const SPLIT_PRIZE_POOL: usize = 1;
+
+/// Revenue split bucket: reserve PDA.
+pub const SPLIT_RESERVE: usize = 2;
+
+/// Number of revenue split buckets.
+pub const NUM_SPLIT_BUCKETS: usize = 3;
+
+/// Basis-point denominator. The split table must sum to exactly this.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Default revenue split: 3% treasury, 96% prize pool, 1% reserve.
+pub const DEFAULT_SPLIT_BPS: [u16; NUM_SPLIT_BUCKETS] = [300, 9_600, 100];
+
+/// Session permission: may call throw_ball_with_session.
+pub const SESSION_ALLOW_THROW: u8 = 1 << 0;
+
+/// All valid session permissions.
+pub const SESSION_ALLOW_ALL: u8 = SESSION_ALLOW_THROW;
+
+/// Default window after the last SolBalls purchase during which balls can be sold back (15 minutes).
+pub const DEFAULT_REFUND_WINDOW: i64 = 15 * 60;
+
+/// Maximum configurable refund window (7 days).
+pub const MAX_REFUND_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+/// Default sell-back haircut (10% of the price paid is kept).
+pub const DEFAULT_REFUND_HAIRCUT_BPS: u16 = 1_000;
+
+/// Maximum lifetime of a session key (7 days).
+pub const MAX_SESSION_DURATION: i64 = 7 * 24 * 60 * 60;
+
+/// Number of payment currencies with spend limits (SolBalls, USDC, SOL), indexed by CURRENCY_*.
+pub const NUM_SPEND_CURRENCIES: usize = 3;
+
+/// Length of the daily spend window (24 hours).
+pub const SPEND_WINDOW_DAY: i64 = 24 * 60 * 60;
+
+/// Length of the weekly spend window (7 days).
+pub const SPEND_WINDOW_WEEK: i64 = 7 * 24 * 60 * 60;
+
+/// Native ed25519 signature-verification program (checked by throw_ball_for).
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
 /// PDA seeds
 pub const GAME_CONFIG_SEED: &[u8] = b"game_config";
 pub const POKEMON_SLOTS_SEED: &[u8] = b"pokemon_slots";
@@ -31,6 +186,14 @@ pub const NFT_VAULT_SEED: &[u8] = b"nft_vault";
 pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const VRF_REQ_SEED: &[u8] = b"vrf_req";
 pub const GAME_SOLBALLS_SEED: &[u8] = b"game_solballs";
+pub const PENDING_CONFIG_SEED: &[u8] = b"pending_config";
+pub const PENDING_PRIZE_SEED: &[u8] = b"pending_prize";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const SOL_TREASURY_SEED: &[u8] = b"sol_treasury";
+pub const PRIZE_POOL_SEED: &[u8] = b"prize_pool";
+pub const RESERVE_SEED: &[u8] = b"reserve";
+pub const SPEND_TRACKER_SEED: &[u8] = b"spend_tracker";
+pub const BALL_MINT_SEED: &[u8] = b"ball_mint";
 
 /// Default ball prices in SolCatch atomic units (placeholder — admin configurable)
 /// SolCatch uses 9 decimals: 1 SOLCATCH = 1_000_000_000 atomic units
@@ -43,3 +206,40 @@ pub const DEFAULT_BALL_PRICES: [u64; 4] = [
 
 /// Default catch rates (percent, 0-100)
 pub const DEFAULT_CATCH_RATES: [u8; 4] = [2, 20, 50, 99];
+
+/// Pokemon rarity: common.
+pub const RARITY_COMMON: u8 = 0;
+
+/// Pokemon rarity: uncommon.
+pub const RARITY_UNCOMMON: u8 = 1;
+
+/// Pokemon rarity: rare.
+pub const RARITY_RARE: u8 = 2;
+
+/// Pokemon rarity: legendary.
+pub const RARITY_LEGENDARY: u8 = 3;
+
+/// Number of rarity tiers.
+pub const NUM_RARITIES: usize = 4;
+
+/// Default VRF spawn odds per rarity in basis points (70% / 20% / 9% / 1%). Must sum to 10,000.
+pub const DEFAULT_RARITY_WEIGHTS: [u16; NUM_RARITIES] = [7_000, 2_000, 900, 100];
+
+/// Default catch-rate multipliers per rarity in basis points (1x / 0.8x / 0.5x / 0.25x).
+pub const DEFAULT_RARITY_CATCH_MULTIPLIERS: [u16; NUM_RARITIES] = [10_000, 8_000, 5_000, 2_500];
+
+/// Largest allowed catch-rate multiplier (2x). The effective rate is capped at 100%.
+pub const MAX_RARITY_CATCH_MULTIPLIER: u16 = 20_000;
+
+/// Number of crafting recipes (each tier into the next: poke->great, great->ultra, ultra->master).
+pub const NUM_CRAFT_RECIPES: usize = NUM_BALL_TYPES - 1;
+
+/// Default balls consumed per crafted ball, roughly value-neutral at the default prices:
+/// 10 Poke -> 1 Great, 3 Great -> 1 Ultra, 2 Ultra -> 1 Master.
+pub const DEFAULT_CRAFT_RATIOS: [u32; NUM_CRAFT_RECIPES] = [10, 3, 2];
+
+/// Smallest allowed crafting ratio (1:1 would make upgrades free).
+pub const MIN_CRAFT_RATIO: u32 = 2;
+
+/// Largest allowed crafting ratio.
+pub const MAX_CRAFT_RATIO: u32 = 1_000;
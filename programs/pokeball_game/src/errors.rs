@@ -77,9 +77,252 @@ pub enum GameError {
     #[msg("Ball price must be greater than 0")]
     ZeroBallPrice,
 
-    #[msg("Unauthorized: only authority can call this instruction")]
+    #[msg("Unauthorized: signer is not the authority or the required role holder")]
     Unauthorized,
 
     #[msg("NFT transfer accounts not found in remaining_accounts for the awarded mint")]
     NftTransferAccountsMissing,
+
+    #[msg("Ball purchases are paused")]
+    PurchasesPaused,
+
+    #[msg("Ball throws are paused")]
+    ThrowsPaused,
+
+    #[msg("Pokemon spawns are paused")]
+    SpawnsPaused,
+
+    #[msg("NFT awards are paused")]
+    NftAwardsPaused,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Invalid pending authority")]
+    InvalidPendingAuthority,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+
+    #[msg("Invalid role. Must be 0-3 (ConfigAdmin, Spawner, VaultManager, Treasurer)")]
+    InvalidRole,
+
+    #[msg("Invalid role holder")]
+    InvalidRoleHolder,
+
+    #[msg("Queued config change is not yet executable")]
+    ConfigChangeNotReady,
+
+    #[msg("Invalid config change delay")]
+    InvalidConfigChangeDelay,
+
+    #[msg("Account is not a VrfRequest owned by this program")]
+    InvalidVrfRequestAccount,
+
+    #[msg("VRF request has not been consumed yet")]
+    VrfRequestPending,
+
+    #[msg("Refund account does not match the VRF request payer")]
+    InvalidRequestPayer,
+
+    #[msg("remaining_accounts has an unexpected length")]
+    InvalidRemainingAccounts,
+
+    #[msg("VRF request has not timed out yet")]
+    RequestNotStale,
+
+    #[msg("ORAO already fulfilled this request; consume it instead")]
+    VrfFulfilledByOracle,
+
+    #[msg("Player inventory account is required for throw requests")]
+    PlayerInventoryMissing,
+
+    #[msg("Invalid VRF timeout")]
+    InvalidVrfTimeout,
+
+    #[msg("Slot is reserved by a pending VRF spawn")]
+    SlotSpawnPending,
+
+    #[msg("Winner account does not match the VRF request's player")]
+    WinnerMismatch,
+
+    #[msg("Player inventory does not belong to the expected player")]
+    InvalidPlayerInventory,
+
+    #[msg("NFT destination token account is not owned by the winner")]
+    NftDestinationOwnerMismatch,
+
+    #[msg("NFT destination token account is for a different mint")]
+    NftDestinationMintMismatch,
+
+    #[msg("Pending prize account is already in use")]
+    PendingPrizeInUse,
+
+    #[msg("Signer is not the configured relayer")]
+    InvalidRelayer,
+
+    #[msg("Relayed throw intent has expired")]
+    RelayIntentExpired,
+
+    #[msg("Relayed throw nonce does not match the player's next nonce")]
+    InvalidRelayNonce,
+
+    #[msg("Expected an ed25519 signature instruction before this one")]
+    MissingEd25519Instruction,
+
+    #[msg("ed25519 instruction does not carry the player's signature over this intent")]
+    InvalidRelaySignature,
+
+    #[msg("Session expiry must be in the future and within the maximum session duration")]
+    InvalidSessionExpiry,
+
+    #[msg("Invalid session permissions")]
+    InvalidSessionPermissions,
+
+    #[msg("Invalid session key")]
+    InvalidSessionKey,
+
+    #[msg("Session has expired")]
+    SessionExpired,
+
+    #[msg("Session is not allowed to perform this action")]
+    SessionNotAllowed,
+
+    #[msg("Session allowance for this ball type is used up")]
+    SessionAllowanceExceeded,
+
+    #[msg("Ball type has no USDC price configured")]
+    UsdcPriceNotSet,
+
+    #[msg("Insufficient USDC balance for purchase")]
+    InsufficientUsdc,
+
+    #[msg("Ball type has no SOL price configured")]
+    SolPriceNotSet,
+
+    #[msg("Insufficient SOL balance for purchase")]
+    InsufficientSol,
+
+    #[msg("Revenue split must sum to 10,000 basis points")]
+    InvalidRevenueSplit,
+
+    #[msg("No revenue to distribute")]
+    NothingToDistribute,
+
+    #[msg("Oracle pricing is not configured")]
+    PriceFeedNotSet,
+
+    #[msg("Price feed account is invalid or not trading")]
+    InvalidPriceFeed,
+
+    #[msg("Oracle price is too old")]
+    StalePrice,
+
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Ball type has no USD price configured")]
+    UsdPriceNotSet,
+
+    #[msg("Total cost exceeds the buyer's maximum")]
+    SlippageExceeded,
+
+    #[msg("Invalid oracle price age or confidence bound")]
+    InvalidPriceBounds,
+
+    #[msg("Destination is not owned by the treasury wallet")]
+    InvalidTreasuryAccount,
+
+    #[msg("Invalid treasury wallet")]
+    InvalidTreasury,
+
+    #[msg("Purchases are blocked by the player's self-exclusion")]
+    SelfExcluded,
+
+    #[msg("Purchase would exceed the daily spend limit")]
+    DailySpendLimitExceeded,
+
+    #[msg("Purchase would exceed the weekly spend limit")]
+    WeeklySpendLimitExceeded,
+
+    #[msg("Daily spend limit cannot exceed the weekly limit")]
+    InvalidSpendLimits,
+
+    #[msg("Self-exclusion must end in the future and cannot be shortened")]
+    InvalidSelfExclusion,
+
+    #[msg("Unknown currency")]
+    InvalidCurrency,
+
+    #[msg("Invalid recipient")]
+    InvalidRecipient,
+
+    #[msg("Ball transfers are paused")]
+    TransfersPaused,
+
+    #[msg("Ball token minting is paused")]
+    BallTokensPaused,
+
+    #[msg("Ball token mint has not been created for this tier")]
+    BallMintNotSet,
+
+    #[msg("Ball mint already created for this tier")]
+    BallMintAlreadySet,
+
+    #[msg("Account is not the ball token mint for this tier")]
+    InvalidBallMint,
+
+    #[msg("Ball mint and player ball token account must be passed together")]
+    BallTokenAccountsMissing,
+
+    #[msg("Ball crafting is paused")]
+    CraftingPaused,
+
+    #[msg("No crafting recipe for this ball type")]
+    CraftNotAvailable,
+
+    #[msg("Crafting ratio must be 0 (disabled) or between 2 and 1000")]
+    InvalidCraftRatio,
+
+    #[msg("Ball refunds are disabled")]
+    RefundsDisabled,
+
+    #[msg("Refund window has expired")]
+    RefundWindowExpired,
+
+    #[msg("Quantity exceeds the balls bought within the refund window")]
+    ExceedsRefundable,
+
+    #[msg("Invalid refund window or haircut")]
+    InvalidRefundPolicy,
+
+    #[msg("Invalid rarity")]
+    InvalidRarity,
+
+    #[msg("Rarity weights must sum to 10,000 bps")]
+    InvalidRarityWeights,
+
+    #[msg("Rarity catch multiplier must be between 1 and 20,000 bps")]
+    InvalidRarityMultiplier,
+
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Account does not match the expected legacy layout")]
+    InvalidAccountLayout,
+
+    #[msg("Recipient has self-excluded from purchases")]
+    RecipientSelfExcluded,
+
+    #[msg("Session account is required to restore a session throw's allowance")]
+    SessionAccountMissing,
+
+    #[msg("Price feed and SolBalls mint accounts are required once USD prices are set")]
+    PriceFeedMissing,
+
+    #[msg("Refund window is still open")]
+    RefundWindowOpen,
 }
@@ -6,6 +6,8 @@ pub struct BallPurchased {
     pub ball_type: u8,
     pub quantity: u32,
     pub total_cost: u64,
+    /// CURRENCY_* the purchase was paid in.
+    pub currency: u8,
 }
 
 #[event]
@@ -23,6 +25,7 @@ pub struct CaughtPokemon {
     pub pokemon_id: u64,
     pub slot_index: u8,
     pub nft_mint: Pubkey,
+    pub rarity: u8,
 }
 
 #[event]
@@ -39,6 +42,7 @@ pub struct PokemonSpawned {
     pub slot_index: u8,
     pub pos_x: u16,
     pub pos_y: u16,
+    pub rarity: u8,
 }
 
 #[event]
@@ -81,6 +85,8 @@ pub struct BallPriceUpdated {
     pub ball_type: u8,
     pub old_price: u64,
     pub new_price: u64,
+    /// CURRENCY_* the price is denominated in.
+    pub currency: u8,
 }
 
 #[event]
@@ -100,4 +106,332 @@ pub struct MaxActivePokemonUpdated {
 pub struct RevenueWithdrawn {
     pub recipient: Pubkey,
     pub amount: u64,
+    /// CURRENCY_* withdrawn.
+    pub currency: u8,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub old_flags: u8,
+    pub new_flags: u8,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: u8,
+    pub holder: Pubkey,
+    pub previous_holder: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: u8,
+    pub holder: Pubkey,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub proposer: Pubkey,
+    pub ball_prices: [u64; 4],
+    pub catch_rates: [u8; 4],
+    pub max_active_pokemon: u8,
+    pub usdc_ball_prices: [u64; 4],
+    pub sol_ball_prices: [u64; 4],
+    pub usd_ball_prices: [u64; 4],
+    pub rarity_weights: [u16; 4],
+    pub rarity_catch_multipliers: [u16; 4],
+    pub price_feed: Pubkey,
+    pub max_price_age: i64,
+    pub max_price_conf_bps: u16,
+    pub refund_window: i64,
+    pub refund_haircut_bps: u16,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub executor: Pubkey,
+    pub ball_prices: [u64; 4],
+    pub catch_rates: [u8; 4],
+    pub max_active_pokemon: u8,
+    pub usdc_ball_prices: [u64; 4],
+    pub sol_ball_prices: [u64; 4],
+    pub usd_ball_prices: [u64; 4],
+    pub rarity_weights: [u16; 4],
+    pub rarity_catch_multipliers: [u16; 4],
+    pub price_feed: Pubkey,
+    pub max_price_age: i64,
+    pub max_price_conf_bps: u16,
+    pub refund_window: i64,
+    pub refund_haircut_bps: u16,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub cancelled_by: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigChangeDelayUpdated {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct VrfRequestClosed {
+    pub vrf_request: Pubkey,
+    pub payer: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct VrfRequestCancelled {
+    pub vrf_request: Pubkey,
+    pub request_type: u8,
+    pub player: Pubkey,
+    pub slot_index: u8,
+    pub ball_type: u8,
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct VrfTimeoutUpdated {
+    pub old_timeout: i64,
+    pub new_timeout: i64,
+}
+
+#[event]
+pub struct ThrowVoided {
+    pub thrower: Pubkey,
+    /// Pokemon the throw was aimed at.
+    pub pokemon_id: u64,
+    pub slot_index: u8,
+    pub ball_type: u8,
+    /// Pokemon now in the slot (0 if the slot is empty).
+    pub current_pokemon_id: u64,
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub winner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub pending_prize: Pubkey,
+    pub claimed_by: Pubkey,
+}
+
+#[event]
+pub struct ThrowRelayed {
+    pub player: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct RelayerUpdated {
+    pub old_relayer: Pubkey,
+    pub new_relayer: Pubkey,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub ball_allowance: [u32; 4],
+    pub allowed_mask: u8,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+}
+
+#[event]
+pub struct RevenueSplitUpdated {
+    pub old_split_bps: [u16; 3],
+    pub new_split_bps: [u16; 3],
+}
+
+#[event]
+pub struct TreasuryRevenueDistributed {
+    pub treasury_wallet: Pubkey,
+    pub amount: u64,
+    pub total_to_treasury: u64,
+}
+
+#[event]
+pub struct PrizePoolRevenueDistributed {
+    pub prize_pool: Pubkey,
+    pub amount: u64,
+    pub total_to_prize_pool: u64,
+}
+
+#[event]
+pub struct ReserveRevenueDistributed {
+    pub reserve: Pubkey,
+    pub amount: u64,
+    pub total_to_reserve: u64,
+}
+
+#[event]
+pub struct PriceFeedUpdated {
+    pub old_price_feed: Pubkey,
+    pub new_price_feed: Pubkey,
+    pub max_price_age: i64,
+    pub max_price_conf_bps: u16,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+}
+
+#[event]
+pub struct BundlePurchased {
+    pub buyer: Pubkey,
+    pub quantities: [u32; 4],
+    pub total_cost: u64,
+}
+
+#[event]
+pub struct SpendLimitsUpdated {
+    pub daily_spend_limits: [u64; 3],
+    pub weekly_spend_limits: [u64; 3],
+}
+
+#[event]
+pub struct SelfExclusionSet {
+    pub player: Pubkey,
+    pub excluded_until: i64,
+}
+
+#[event]
+pub struct BallsGifted {
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub ball_type: u8,
+    pub quantity: u32,
+    pub total_cost: u64,
+}
+
+#[event]
+pub struct BallsSent {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub ball_type: u8,
+    pub quantity: u32,
+    pub remaining: u32,
+}
+
+#[event]
+pub struct BallsReceived {
+    pub recipient: Pubkey,
+    pub sender: Pubkey,
+    pub ball_type: u8,
+    pub quantity: u32,
+    pub new_balance: u32,
+}
+
+#[event]
+pub struct BallMintCreated {
+    pub ball_type: u8,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct BallTokensMinted {
+    pub player: Pubkey,
+    pub ball_type: u8,
+    pub quantity: u32,
+}
+
+#[event]
+pub struct BallTokensRedeemed {
+    pub player: Pubkey,
+    pub ball_type: u8,
+    pub quantity: u32,
+}
+
+#[event]
+pub struct BallsCrafted {
+    pub player: Pubkey,
+    pub from_ball_type: u8,
+    pub to_ball_type: u8,
+    pub consumed: u32,
+    pub produced: u32,
+}
+
+#[event]
+pub struct CraftRatiosUpdated {
+    pub old_ratios: [u32; 3],
+    pub new_ratios: [u32; 3],
+}
+
+#[event]
+pub struct BallsRefunded {
+    pub player: Pubkey,
+    pub ball_type: u8,
+    pub quantity: u32,
+    pub refund_amount: u64,
+    pub haircut_amount: u64,
+}
+
+#[event]
+pub struct RefundPolicyUpdated {
+    pub refund_window: i64,
+    pub refund_haircut_bps: u16,
+}
+
+#[event]
+pub struct RarityConfigUpdated {
+    pub old_weights: [u16; 4],
+    pub new_weights: [u16; 4],
+    pub old_catch_multipliers: [u16; 4],
+    pub new_catch_multipliers: [u16; 4],
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event]
+pub struct SplitBucketWithdrawn {
+    /// SPLIT_PRIZE_POOL or SPLIT_RESERVE.
+    pub bucket: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct RefundsExpired {
+    pub player: Pubkey,
+    /// SolBalls released from GameConfig.outstanding_refundable.
+    pub released: u64,
+    pub outstanding_refundable: u64,
 }
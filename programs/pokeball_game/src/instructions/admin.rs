@@ -4,101 +4,182 @@ use crate::state::*;
 use crate::errors::GameError;
 use crate::events::*;
 use crate::constants::*;
+use crate::instructions::authority::AuthorityConfig;
 
+/// Accounts for configuration changes. Requires the ConfigAdmin role.
 #[derive(Accounts)]
 pub struct AdminConfig<'info> {
     pub authority: Signer<'info>,
 
     #[account(
         mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_CONFIG_ADMIN, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+}
+
+/// Accounts for changing the treasury wallet. Requires the authority.
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
         seeds = [GAME_CONFIG_SEED],
         bump = game_config.bump,
         constraint = game_config.is_initialized @ GameError::NotInitialized,
         constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
     )]
     pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
 }
 
-pub fn set_ball_price_handler(
-    ctx: Context<AdminConfig>,
-    ball_type: u8,
-    new_price: u64,
+pub fn set_pause_flags_handler(
+    ctx: Context<AuthorityConfig>,
+    pause_flags: u8,
 ) -> Result<()> {
     require!(
-        (ball_type as usize) < NUM_BALL_TYPES,
-        GameError::InvalidBallType
+        pause_flags & !PAUSE_ALL == 0,
+        GameError::InvalidPauseFlags
     );
-    require!(new_price > 0, GameError::ZeroBallPrice);
 
     let game_config = &mut ctx.accounts.game_config;
-    let old_price = game_config.ball_prices[ball_type as usize];
-    game_config.ball_prices[ball_type as usize] = new_price;
+    let old_flags = game_config.pause_flags;
+    game_config.pause_flags = pause_flags;
 
-    emit!(BallPriceUpdated {
-        ball_type,
-        old_price,
-        new_price,
+    emit!(PauseFlagsUpdated {
+        old_flags,
+        new_flags: pause_flags,
     });
 
     msg!(
-        "Ball type {} price updated: {} -> {}",
-        ball_type, old_price, new_price
+        "Pause flags updated: {:#06b} -> {:#06b}",
+        old_flags, pause_flags
     );
 
     Ok(())
 }
 
-pub fn set_catch_rate_handler(
+pub fn set_vrf_timeout_handler(
     ctx: Context<AdminConfig>,
-    ball_type: u8,
-    new_rate: u8,
+    new_timeout: i64,
 ) -> Result<()> {
     require!(
-        (ball_type as usize) < NUM_BALL_TYPES,
-        GameError::InvalidBallType
+        (MIN_VRF_TIMEOUT..=MAX_VRF_TIMEOUT).contains(&new_timeout),
+        GameError::InvalidVrfTimeout
     );
-    require!(new_rate <= 100, GameError::InvalidCatchRate);
 
     let game_config = &mut ctx.accounts.game_config;
-    let old_rate = game_config.catch_rates[ball_type as usize];
-    game_config.catch_rates[ball_type as usize] = new_rate;
+    let old_timeout = game_config.vrf_timeout;
+    game_config.vrf_timeout = new_timeout;
 
-    emit!(CatchRateUpdated {
-        ball_type,
-        old_rate,
-        new_rate,
+    emit!(VrfTimeoutUpdated {
+        old_timeout,
+        new_timeout,
     });
 
-    msg!(
-        "Ball type {} catch rate updated: {}% -> {}%",
-        ball_type, old_rate, new_rate
-    );
+    msg!("VRF timeout updated: {}s -> {}s", old_timeout, new_timeout);
 
     Ok(())
 }
 
-pub fn set_max_active_pokemon_handler(
+pub fn set_relayer_handler(
     ctx: Context<AdminConfig>,
-    new_max: u8,
+    new_relayer: Pubkey,
 ) -> Result<()> {
-    require!(
-        new_max >= 1 && new_max <= MAX_POKEMON_SLOTS as u8,
-        GameError::InvalidMaxActivePokemon
-    );
+    let game_config = &mut ctx.accounts.game_config;
+    let old_relayer = game_config.relayer;
+    game_config.relayer = new_relayer;
+
+    emit!(RelayerUpdated {
+        old_relayer,
+        new_relayer,
+    });
+
+    msg!("Relayer updated: {} -> {}", old_relayer, new_relayer);
+
+    Ok(())
+}
+
+pub fn set_treasury_handler(
+    ctx: Context<SetTreasury>,
+    new_treasury: Pubkey,
+) -> Result<()> {
+    require!(new_treasury != Pubkey::default(), GameError::InvalidTreasury);
+
+    let treasury_config = &mut ctx.accounts.treasury_config;
+    let old_treasury = treasury_config.treasury_wallet;
+    treasury_config.treasury_wallet = new_treasury;
+
+    emit!(TreasuryUpdated {
+        old_treasury,
+        new_treasury,
+    });
+
+    msg!("Treasury updated: {} -> {}", old_treasury, new_treasury);
+
+    Ok(())
+}
+
+pub fn set_spend_limits_handler(
+    ctx: Context<AdminConfig>,
+    daily_spend_limits: [u64; NUM_SPEND_CURRENCIES],
+    weekly_spend_limits: [u64; NUM_SPEND_CURRENCIES],
+) -> Result<()> {
+    // 0 means unlimited; a capped day cannot allow more than a capped week
+    for (daily, weekly) in daily_spend_limits.iter().zip(weekly_spend_limits.iter()) {
+        require!(
+            *daily == 0 || *weekly == 0 || daily <= weekly,
+            GameError::InvalidSpendLimits
+        );
+    }
 
     let game_config = &mut ctx.accounts.game_config;
-    let old_max = game_config.max_active_pokemon;
-    game_config.max_active_pokemon = new_max;
+    game_config.daily_spend_limits = daily_spend_limits;
+    game_config.weekly_spend_limits = weekly_spend_limits;
 
-    emit!(MaxActivePokemonUpdated {
-        old_max,
-        new_max,
+    emit!(SpendLimitsUpdated {
+        daily_spend_limits,
+        weekly_spend_limits,
     });
 
     msg!(
-        "Max active Pokemon updated: {} -> {}",
-        old_max, new_max
+        "Spend limits updated: daily {:?}, weekly {:?}",
+        daily_spend_limits, weekly_spend_limits
     );
 
     Ok(())
 }
+
+pub fn set_craft_ratios_handler(
+    ctx: Context<AdminConfig>,
+    new_ratios: [u32; NUM_CRAFT_RECIPES],
+) -> Result<()> {
+    for ratio in new_ratios.iter() {
+        require!(
+            *ratio == 0 || (*ratio >= MIN_CRAFT_RATIO && *ratio <= MAX_CRAFT_RATIO),
+            GameError::InvalidCraftRatio
+        );
+    }
+
+    let game_config = &mut ctx.accounts.game_config;
+    let old_ratios = game_config.craft_ratios;
+    game_config.craft_ratios = new_ratios;
+
+    emit!(CraftRatiosUpdated {
+        old_ratios,
+        new_ratios,
+    });
+
+    msg!("Craft ratios updated: {:?} -> {:?}", old_ratios, new_ratios);
+
+    Ok(())
+}
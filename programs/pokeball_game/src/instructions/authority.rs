@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::*;
+use crate::constants::*;
+
+/// Accounts for instructions only the top-level authority may call
+/// (authority transfer, role management, pausing).
+#[derive(Accounts)]
+pub struct AuthorityConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// The proposed authority. Must sign to prove it controls the key.
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.pending_authority != Pubkey::default() @ GameError::NoPendingAuthority,
+        constraint = game_config.pending_authority == new_authority.key() @ GameError::NotPendingAuthority,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// NFT vault — its authority moves together with the config authority.
+    #[account(
+        mut,
+        seeds = [NFT_VAULT_SEED],
+        bump = nft_vault.bump,
+    )]
+    pub nft_vault: Box<Account<'info, NftVault>>,
+}
+
+pub fn propose_authority_handler(
+    ctx: Context<AuthorityConfig>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    require!(
+        new_authority != Pubkey::default() && new_authority != game_config.authority,
+        GameError::InvalidPendingAuthority
+    );
+
+    game_config.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposed {
+        authority: game_config.authority,
+        pending_authority: new_authority,
+    });
+
+    msg!(
+        "Authority transfer proposed: {} -> {}",
+        game_config.authority, new_authority
+    );
+
+    Ok(())
+}
+
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.key();
+
+    let game_config = &mut ctx.accounts.game_config;
+    let old_authority = game_config.authority;
+    game_config.authority = new_authority;
+    game_config.pending_authority = Pubkey::default();
+
+    let nft_vault = &mut ctx.accounts.nft_vault;
+    nft_vault.authority = new_authority;
+
+    emit!(AuthorityTransferred {
+        old_authority,
+        new_authority,
+    });
+
+    msg!(
+        "Authority transferred: {} -> {}",
+        old_authority, new_authority
+    );
+
+    Ok(())
+}
+
+pub fn cancel_authority_transfer_handler(ctx: Context<AuthorityConfig>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let cancelled_authority = game_config.pending_authority;
+    require!(
+        cancelled_authority != Pubkey::default(),
+        GameError::NoPendingAuthority
+    );
+
+    game_config.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferCancelled {
+        authority: game_config.authority,
+        cancelled_authority,
+    });
+
+    msg!("Authority transfer to {} cancelled", cancelled_authority);
+
+    Ok(())
+}
+
+pub fn grant_role_handler(
+    ctx: Context<AuthorityConfig>,
+    role: u8,
+    holder: Pubkey,
+) -> Result<()> {
+    require!((role as usize) < NUM_ROLES, GameError::InvalidRole);
+    require!(holder != Pubkey::default(), GameError::InvalidRoleHolder);
+
+    let game_config = &mut ctx.accounts.game_config;
+    let previous_holder = game_config.roles[role as usize];
+    game_config.roles[role as usize] = holder;
+
+    emit!(RoleGranted {
+        role,
+        holder,
+        previous_holder,
+    });
+
+    msg!("Role {} granted to {} (was {})", role, holder, previous_holder);
+
+    Ok(())
+}
+
+pub fn revoke_role_handler(
+    ctx: Context<AuthorityConfig>,
+    role: u8,
+) -> Result<()> {
+    require!((role as usize) < NUM_ROLES, GameError::InvalidRole);
+
+    let game_config = &mut ctx.accounts.game_config;
+    let holder = game_config.roles[role as usize];
+    require!(holder != Pubkey::default(), GameError::InvalidRoleHolder);
+    game_config.roles[role as usize] = Pubkey::default();
+
+    emit!(RoleRevoked {
+        role,
+        holder,
+    });
+
+    msg!("Role {} revoked from {}", role, holder);
+
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::*;
+use crate::constants::*;
+
+/// Accounts for creating a tier's ball token mint. Requires the ConfigAdmin role.
+#[derive(Accounts)]
+#[instruction(ball_type: u8)]
+pub struct CreateBallMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_CONFIG_ADMIN, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// Ball token mint PDA. Mint authority is game_config; no freeze authority.
+    #[account(
+        init,
+        payer = authority,
+        seeds = [BALL_MINT_SEED, &[ball_type]],
+        bump,
+        mint::decimals = BALL_TOKEN_DECIMALS,
+        mint::authority = game_config,
+    )]
+    pub ball_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ball_type: u8)]
+pub struct WithdrawBallTokens<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump = player_inventory.bump,
+        constraint = player_inventory.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    #[account(
+        mut,
+        seeds = [BALL_MINT_SEED, &[ball_type]],
+        bump,
+    )]
+    pub ball_mint: Account<'info, Mint>,
+
+    /// Player's ball token ATA. Created on first withdrawal.
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = ball_mint,
+        associated_token::authority = player,
+    )]
+    pub player_ball_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ball_type: u8)]
+pub struct RedeemBallTokens<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// Player inventory PDA. Created here for wallets that bought tokens elsewhere.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerInventory::LEN,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    #[account(
+        mut,
+        seeds = [BALL_MINT_SEED, &[ball_type]],
+        bump,
+    )]
+    pub ball_mint: Account<'info, Mint>,
+
+    /// Player's ball token account (source of the burn).
+    #[account(
+        mut,
+        token::mint = ball_mint,
+        token::authority = player,
+    )]
+    pub player_ball_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_mint_handler(
+    ctx: Context<CreateBallMint>,
+    ball_type: u8,
+) -> Result<()> {
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    let mint = ctx.accounts.ball_mint.key();
+    let game_config = &mut ctx.accounts.game_config;
+    require!(
+        game_config.ball_mints[ball_type as usize] == Pubkey::default(),
+        GameError::BallMintAlreadySet
+    );
+    game_config.ball_mints[ball_type as usize] = mint;
+
+    emit!(BallMintCreated {
+        ball_type,
+        mint,
+    });
+
+    msg!("Ball mint for type {} created: {}", ball_type, mint);
+
+    Ok(())
+}
+
+pub fn withdraw_handler(
+    ctx: Context<WithdrawBallTokens>,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    let game_config = &ctx.accounts.game_config;
+    require!(
+        !game_config.is_paused(PAUSE_BALL_TOKENS),
+        GameError::BallTokensPaused
+    );
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+    require!(
+        game_config.ball_mints[ball_type as usize] == ctx.accounts.ball_mint.key(),
+        GameError::BallMintNotSet
+    );
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    // Debit inventory
+    ctx.accounts.player_inventory.debit_balls(&mut ctx.accounts.game_config, ball_type, quantity)?;
+
+    mint_ball_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.ball_mint.to_account_info(),
+        ctx.accounts.player_ball_account.to_account_info(),
+        ctx.accounts.game_config.to_account_info(),
+        ctx.accounts.game_config.bump,
+        quantity,
+    )?;
+
+    emit!(BallTokensMinted {
+        player: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+    });
+
+    msg!(
+        "Player {} withdrew {} balls of type {} as tokens",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type
+    );
+
+    Ok(())
+}
+
+pub fn redeem_handler(
+    ctx: Context<RedeemBallTokens>,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_BALL_TOKENS),
+        GameError::BallTokensPaused
+    );
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+    require!(
+        ctx.accounts.game_config.ball_mints[ball_type as usize] == ctx.accounts.ball_mint.key(),
+        GameError::BallMintNotSet
+    );
+    require!(quantity > 0, GameError::ZeroQuantity);
+    require!(
+        ctx.accounts.player_ball_account.amount >= quantity as u64,
+        GameError::InsufficientBalls
+    );
+
+    // Burn the tokens (player signs as token owner)
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.ball_mint.to_account_info(),
+            from: ctx.accounts.player_ball_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, quantity as u64)?;
+
+    // Credit inventory (not counted as a purchase)
+    let inventory = &mut ctx.accounts.player_inventory;
+    if inventory.player == Pubkey::default() {
+        // First-time initialization
+        inventory.player = ctx.accounts.player.key();
+        inventory.bump = ctx.bumps.player_inventory;
+    }
+    inventory.balls[ball_type as usize] = inventory.balls[ball_type as usize]
+        .checked_add(quantity)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(BallTokensRedeemed {
+        player: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+    });
+
+    msg!(
+        "Player {} redeemed {} ball tokens of type {}",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type
+    );
+
+    Ok(())
+}
+
+/// Mint `quantity` ball tokens to `to`, signed by the game_config PDA.
+pub(crate) fn mint_ball_tokens<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    game_config: AccountInfo<'info>,
+    game_config_bump: u8,
+    quantity: u32,
+) -> Result<()> {
+    let config_seeds = &[
+        GAME_CONFIG_SEED,
+        &[game_config_bump],
+    ];
+    let signer_seeds = &[&config_seeds[..]];
+
+    let mint_ctx = CpiContext::new_with_signer(
+        token_program,
+        MintTo {
+            mint,
+            to,
+            authority: game_config,
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, quantity as u64)
+}
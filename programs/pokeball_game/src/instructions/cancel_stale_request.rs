@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use orao_solana_vrf::state::RandomnessAccountData;
+use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::VrfRequestCancelled;
+use crate::constants::*;
+use crate::instructions::session::restore_session_allowance;
+
+#[derive(Accounts)]
+pub struct CancelStaleRequest<'info> {
+    /// Anyone can cancel once the request has timed out.
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [POKEMON_SLOTS_SEED],
+        bump = pokemon_slots.bump,
+    )]
+    pub pokemon_slots: Box<Account<'info, PokemonSlots>>,
+
+    #[account(
+        mut,
+        constraint = !vrf_request.is_fulfilled @ GameError::VrfAlreadyFulfilled,
+        close = request_payer,
+    )]
+    pub vrf_request: Account<'info, VrfRequest>,
+
+    /// Original payer of the VRF request account — receives its rent.
+    /// CHECK: Validated against vrf_request.payer.
+    #[account(
+        mut,
+        address = vrf_request.payer,
+    )]
+    pub request_payer: AccountInfo<'info>,
+
+    /// ORAO VRF randomness account for this request. Must NOT be fulfilled,
+    /// otherwise a player could cancel a known miss to get the ball back.
+    /// CHECK: Seeds are validated to ensure this is the correct ORAO randomness PDA.
+    #[account(
+        seeds = [RANDOMNESS_ACCOUNT_SEED, vrf_request.seed.as_ref()],
+        bump,
+        seeds::program = orao_solana_vrf::ID,
+    )]
+    pub vrf_randomness: AccountInfo<'info>,
+
+    /// Thrower's inventory — required for throw requests to refund the ball.
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, vrf_request.player.as_ref()],
+        bump = player_inventory.bump,
+    )]
+    pub player_inventory: Option<Account<'info, PlayerInventory>>,
+
+    /// SessionKey that paid for a session throw, to restore its allowance.
+    /// Required when vrf_request.session is set.
+    /// CHECK: Address validated against vrf_request.session; deserialized in the handler.
+    #[account(
+        mut,
+        address = vrf_request.session,
+    )]
+    pub session: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<CancelStaleRequest>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vrf_request = &ctx.accounts.vrf_request;
+
+    // Check the request has timed out
+    let deadline = vrf_request.requested_at
+        .checked_add(ctx.accounts.game_config.vrf_timeout)
+        .ok_or(GameError::MathOverflow)?;
+    require!(clock.unix_timestamp >= deadline, GameError::RequestNotStale);
+
+    // Check ORAO really never fulfilled it (account may not even exist).
+    // An existing account that fails to parse is rejected rather than
+    // treated as unfulfilled.
+    let randomness_info = &ctx.accounts.vrf_randomness;
+    if !randomness_info.data_is_empty() {
+        let data = randomness_info.try_borrow_data()?;
+        let randomness_data = RandomnessAccountData::try_deserialize(&mut data.as_ref())?;
+        require!(
+            randomness_data.fulfilled_randomness().is_none(),
+            GameError::VrfFulfilledByOracle
+        );
+    }
+
+    let request_type = vrf_request.request_type;
+    let player = vrf_request.player;
+    let slot_index = vrf_request.slot_index;
+    let ball_type = vrf_request.ball_type;
+    let requested_at = vrf_request.requested_at;
+
+    match request_type {
+        VRF_TYPE_THROW => {
+            require!(
+                (ball_type as usize) < NUM_BALL_TYPES,
+                GameError::InvalidBallType
+            );
+
+            // Refund the ball and roll back the throw count
+            let inventory = ctx.accounts.player_inventory
+                .as_mut()
+                .ok_or(GameError::PlayerInventoryMissing)?;
+            inventory.balls[ball_type as usize] = inventory.balls[ball_type as usize]
+                .checked_add(1)
+                .ok_or(GameError::MathOverflow)?;
+            inventory.total_throws = inventory.total_throws.saturating_sub(1);
+
+            restore_session_allowance(
+                ctx.accounts.session.as_ref().map(|s| s.to_account_info()),
+                &ctx.accounts.vrf_request,
+            )?;
+        }
+        VRF_TYPE_SPAWN => {
+            require!(
+                (slot_index as usize) < MAX_POKEMON_SLOTS,
+                GameError::InvalidSlotIndex
+            );
+
+            // Release the slot reservation
+            ctx.accounts.pokemon_slots.set_spawn_pending(slot_index as usize, false);
+        }
+        _ => return Err(GameError::InvalidVrfRequestType.into()),
+    }
+
+    emit!(VrfRequestCancelled {
+        vrf_request: ctx.accounts.vrf_request.key(),
+        request_type,
+        player,
+        slot_index,
+        ball_type,
+        requested_at,
+    });
+
+    msg!(
+        "Stale VRF request cancelled (type {}, slot {}, player {})",
+        request_type, slot_index, player
+    );
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::associated_token::AssociatedToken;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::PrizeClaimed;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ClaimPrize<'info> {
+    /// The winner or any cranker. Pays for the winner's ATA if it doesn't exist.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        seeds = [NFT_VAULT_SEED],
+        bump = nft_vault.bump,
+    )]
+    pub nft_vault: Box<Account<'info, NftVault>>,
+
+    /// Closed on success; rent goes back to whoever paid for it.
+    #[account(
+        mut,
+        seeds = [PENDING_PRIZE_SEED, pending_prize.vrf_request.as_ref()],
+        bump = pending_prize.bump,
+        close = rent_payer,
+    )]
+    pub pending_prize: Account<'info, PendingPrize>,
+
+    /// Original payer of the prize account — receives its rent.
+    /// CHECK: Validated against pending_prize.rent_payer.
+    #[account(
+        mut,
+        address = pending_prize.rent_payer,
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    /// The winner's wallet.
+    /// CHECK: Validated against pending_prize.winner.
+    #[account(
+        constraint = winner.key() == pending_prize.winner @ GameError::WinnerMismatch,
+    )]
+    pub winner: AccountInfo<'info>,
+
+    #[account(
+        address = pending_prize.nft_mint,
+    )]
+    pub nft_mint: Account<'info, Mint>,
+
+    /// Vault's NFT token account (source, PDA-owned ATA).
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = nft_vault,
+    )]
+    pub vault_nft_account: Account<'info, TokenAccount>,
+
+    /// Winner's NFT token account (destination).
+    /// Created if it doesn't exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = winner,
+    )]
+    pub winner_nft_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimPrize>) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_NFT_AWARDS),
+        GameError::NftAwardsPaused
+    );
+
+    // Transfer NFT from vault to winner using PDA signer
+    let vault_seeds = &[
+        NFT_VAULT_SEED,
+        &[ctx.accounts.nft_vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_nft_account.to_account_info(),
+            to: ctx.accounts.winner_nft_account.to_account_info(),
+            authority: ctx.accounts.nft_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, 1)?;
+
+    let winner = ctx.accounts.pending_prize.winner;
+    let nft_mint = ctx.accounts.pending_prize.nft_mint;
+
+    emit!(PrizeClaimed {
+        winner,
+        nft_mint,
+        pending_prize: ctx.accounts.pending_prize.key(),
+        claimed_by: ctx.accounts.payer.key(),
+    });
+
+    msg!("Prize NFT {} delivered to {}", nft_mint, winner);
+
+    Ok(())
+}
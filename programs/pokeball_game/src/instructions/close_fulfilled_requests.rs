@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::VrfRequestClosed;
+use crate::instructions::migrate::LegacyVrfRequest;
+
+#[derive(Accounts)]
+pub struct CloseFulfilledRequests<'info> {
+    /// Anyone can crank this sweep.
+    pub cranker: Signer<'info>,
+    // remaining_accounts: pairs per request:
+    //   [0] VrfRequest account (writable)
+    //   [1] Its original payer (writable) — receives the rent
+}
+
+/// Read (is_fulfilled, payer) from either the current or the legacy layout.
+fn read_request(info: &AccountInfo) -> Result<(bool, Pubkey)> {
+    require!(*info.owner == crate::ID, GameError::InvalidVrfRequestAccount);
+
+    let data = info.try_borrow_data()?;
+    require!(
+        data.starts_with(VrfRequest::DISCRIMINATOR),
+        GameError::InvalidVrfRequestAccount
+    );
+
+    if data.len() == LegacyVrfRequest::LEN {
+        let legacy = LegacyVrfRequest::deserialize(&mut &data[8..])
+            .map_err(|_| GameError::InvalidVrfRequestAccount)?;
+        Ok((legacy.is_fulfilled, legacy.player))
+    } else {
+        let request = VrfRequest::try_deserialize(&mut data.as_ref())
+            .map_err(|_| GameError::InvalidVrfRequestAccount)?;
+        Ok((request.is_fulfilled, request.payer))
+    }
+}
+
+/// Close a program-owned account, moving its lamports to `destination`.
+pub(crate) fn close_account<'info>(
+    info: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = info.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(GameError::MathOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.assign(&System::id());
+    info.resize(0)?;
+    Ok(())
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseFulfilledRequests<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(2),
+        GameError::InvalidRemainingAccounts
+    );
+
+    for pair in remaining.chunks(2) {
+        let request_info = &pair[0];
+        let payer_info = &pair[1];
+
+        let (is_fulfilled, payer) = read_request(request_info)?;
+        require!(is_fulfilled, GameError::VrfRequestPending);
+        require!(payer_info.key() == payer, GameError::InvalidRequestPayer);
+
+        let lamports = request_info.lamports();
+        close_account(request_info, payer_info)?;
+
+        emit!(VrfRequestClosed {
+            vrf_request: request_info.key(),
+            payer,
+            lamports,
+        });
+    }
+
+    msg!("Closed {} fulfilled VRF request(s)", remaining.len() / 2);
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::*;
+use crate::constants::*;
+use crate::instructions::authority::AuthorityConfig;
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_CONFIG_ADMIN, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// Pending change PDA. `init` ensures only one change is queued at a time.
+    #[account(
+        init,
+        payer = authority,
+        space = PendingConfigChange::LEN,
+        seeds = [PENDING_CONFIG_SEED],
+        bump,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    /// Anyone can execute once the eta has passed.
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_CONFIG_SEED],
+        bump = pending_config_change.bump,
+        close = proposer,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// Original proposer — receives the pending account's rent.
+    /// CHECK: Validated against pending_config_change.proposer.
+    #[account(
+        mut,
+        address = pending_config_change.proposer,
+    )]
+    pub proposer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_CONFIG_ADMIN, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_CONFIG_SEED],
+        bump = pending_config_change.bump,
+        close = proposer,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// Original proposer — receives the pending account's rent.
+    /// CHECK: Validated against pending_config_change.proposer.
+    #[account(
+        mut,
+        address = pending_config_change.proposer,
+    )]
+    pub proposer: AccountInfo<'info>,
+}
+
+pub fn queue_handler(
+    ctx: Context<QueueConfigChange>,
+    values: ConfigValues,
+) -> Result<()> {
+    values.validate()?;
+
+    let clock = Clock::get()?;
+    let eta = clock.unix_timestamp
+        .checked_add(ctx.accounts.game_config.config_change_delay_at(clock.unix_timestamp))
+        .ok_or(GameError::MathOverflow)?;
+
+    let pending = &mut ctx.accounts.pending_config_change;
+    pending.proposer = ctx.accounts.authority.key();
+    pending.values = values;
+    pending.queued_at = clock.unix_timestamp;
+    pending.eta = eta;
+    pending.bump = ctx.bumps.pending_config_change;
+
+    emit!(ConfigChangeQueued {
+        proposer: pending.proposer,
+        ball_prices: values.ball_prices,
+        catch_rates: values.catch_rates,
+        max_active_pokemon: values.max_active_pokemon,
+        usdc_ball_prices: values.usdc_ball_prices,
+        sol_ball_prices: values.sol_ball_prices,
+        usd_ball_prices: values.usd_ball_prices,
+        rarity_weights: values.rarity_weights,
+        rarity_catch_multipliers: values.rarity_catch_multipliers,
+        price_feed: values.price_feed,
+        max_price_age: values.max_price_age,
+        max_price_conf_bps: values.max_price_conf_bps,
+        refund_window: values.refund_window,
+        refund_haircut_bps: values.refund_haircut_bps,
+        eta,
+    });
+
+    msg!("Config change queued by {}. ETA: {}", pending.proposer, eta);
+
+    Ok(())
+}
+
+pub fn execute_handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pending = &ctx.accounts.pending_config_change;
+    require!(
+        clock.unix_timestamp >= pending.eta,
+        GameError::ConfigChangeNotReady
+    );
+
+    let values = pending.values;
+    let game_config = &mut ctx.accounts.game_config;
+
+    for ball_type in 0..NUM_BALL_TYPES {
+        let old_price = game_config.ball_prices[ball_type];
+        let new_price = values.ball_prices[ball_type];
+        if old_price != new_price {
+            game_config.ball_prices[ball_type] = new_price;
+            emit!(BallPriceUpdated {
+                ball_type: ball_type as u8,
+                old_price,
+                new_price,
+                currency: CURRENCY_SOLBALLS,
+            });
+        }
+
+        let old_usdc_price = game_config.usdc_ball_prices[ball_type];
+        let new_usdc_price = values.usdc_ball_prices[ball_type];
+        if old_usdc_price != new_usdc_price {
+            game_config.usdc_ball_prices[ball_type] = new_usdc_price;
+            emit!(BallPriceUpdated {
+                ball_type: ball_type as u8,
+                old_price: old_usdc_price,
+                new_price: new_usdc_price,
+                currency: CURRENCY_USDC,
+            });
+        }
+
+        let old_sol_price = game_config.sol_ball_prices[ball_type];
+        let new_sol_price = values.sol_ball_prices[ball_type];
+        if old_sol_price != new_sol_price {
+            game_config.sol_ball_prices[ball_type] = new_sol_price;
+            emit!(BallPriceUpdated {
+                ball_type: ball_type as u8,
+                old_price: old_sol_price,
+                new_price: new_sol_price,
+                currency: CURRENCY_SOL,
+            });
+        }
+
+        let old_usd_price = game_config.usd_ball_prices[ball_type];
+        let new_usd_price = values.usd_ball_prices[ball_type];
+        if old_usd_price != new_usd_price {
+            game_config.usd_ball_prices[ball_type] = new_usd_price;
+            emit!(BallPriceUpdated {
+                ball_type: ball_type as u8,
+                old_price: old_usd_price,
+                new_price: new_usd_price,
+                currency: CURRENCY_USD,
+            });
+        }
+
+        let old_rate = game_config.catch_rates[ball_type];
+        let new_rate = values.catch_rates[ball_type];
+        if old_rate != new_rate {
+            game_config.catch_rates[ball_type] = new_rate;
+            emit!(CatchRateUpdated {
+                ball_type: ball_type as u8,
+                old_rate,
+                new_rate,
+            });
+        }
+    }
+
+    let old_max = game_config.max_active_pokemon;
+    if old_max != values.max_active_pokemon {
+        game_config.max_active_pokemon = values.max_active_pokemon;
+        emit!(MaxActivePokemonUpdated {
+            old_max,
+            new_max: values.max_active_pokemon,
+        });
+    }
+
+    let old_weights = game_config.rarity_weights;
+    let old_catch_multipliers = game_config.rarity_catch_multipliers;
+    if old_weights != values.rarity_weights
+        || old_catch_multipliers != values.rarity_catch_multipliers
+    {
+        game_config.rarity_weights = values.rarity_weights;
+        game_config.rarity_catch_multipliers = values.rarity_catch_multipliers;
+        emit!(RarityConfigUpdated {
+            old_weights,
+            new_weights: values.rarity_weights,
+            old_catch_multipliers,
+            new_catch_multipliers: values.rarity_catch_multipliers,
+        });
+    }
+
+    let old_price_feed = game_config.price_feed;
+    if old_price_feed != values.price_feed
+        || game_config.max_price_age != values.max_price_age
+        || game_config.max_price_conf_bps != values.max_price_conf_bps
+    {
+        game_config.price_feed = values.price_feed;
+        game_config.max_price_age = values.max_price_age;
+        game_config.max_price_conf_bps = values.max_price_conf_bps;
+        emit!(PriceFeedUpdated {
+            old_price_feed,
+            new_price_feed: values.price_feed,
+            max_price_age: values.max_price_age,
+            max_price_conf_bps: values.max_price_conf_bps,
+        });
+    }
+
+    if game_config.refund_window != values.refund_window
+        || game_config.refund_haircut_bps != values.refund_haircut_bps
+    {
+        game_config.refund_window = values.refund_window;
+        game_config.refund_haircut_bps = values.refund_haircut_bps;
+        emit!(RefundPolicyUpdated {
+            refund_window: values.refund_window,
+            refund_haircut_bps: values.refund_haircut_bps,
+        });
+    }
+
+    emit!(ConfigChangeExecuted {
+        executor: ctx.accounts.executor.key(),
+        ball_prices: values.ball_prices,
+        catch_rates: values.catch_rates,
+        max_active_pokemon: values.max_active_pokemon,
+        usdc_ball_prices: values.usdc_ball_prices,
+        sol_ball_prices: values.sol_ball_prices,
+        usd_ball_prices: values.usd_ball_prices,
+        rarity_weights: values.rarity_weights,
+        rarity_catch_multipliers: values.rarity_catch_multipliers,
+        price_feed: values.price_feed,
+        max_price_age: values.max_price_age,
+        max_price_conf_bps: values.max_price_conf_bps,
+        refund_window: values.refund_window,
+        refund_haircut_bps: values.refund_haircut_bps,
+    });
+
+    msg!("Config change executed by {}", ctx.accounts.executor.key());
+
+    Ok(())
+}
+
+pub fn cancel_handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let eta = ctx.accounts.pending_config_change.eta;
+
+    emit!(ConfigChangeCancelled {
+        cancelled_by: ctx.accounts.authority.key(),
+        eta,
+    });
+
+    msg!("Config change (ETA {}) cancelled", eta);
+
+    Ok(())
+}
+
+pub fn set_delay_handler(
+    ctx: Context<AuthorityConfig>,
+    new_delay: i64,
+) -> Result<()> {
+    require!(
+        (MIN_CONFIG_CHANGE_DELAY..=MAX_CONFIG_CHANGE_DELAY).contains(&new_delay),
+        GameError::InvalidConfigChangeDelay
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let game_config = &mut ctx.accounts.game_config;
+    let old_delay = game_config.config_change_delay_at(now);
+
+    // A reduction waits out the delay it replaces, so it cannot be used to
+    // queue and execute a change faster than the old timelock allowed
+    let mut effective_at = now;
+    if new_delay < old_delay {
+        effective_at = now.checked_add(old_delay).ok_or(GameError::MathOverflow)?;
+        game_config.config_change_delay_floor = old_delay;
+        game_config.config_change_delay_floor_until = effective_at;
+    }
+    game_config.config_change_delay = new_delay;
+
+    emit!(ConfigChangeDelayUpdated {
+        old_delay,
+        new_delay,
+        effective_at,
+    });
+
+    msg!(
+        "Config change delay updated: {}s -> {}s (effective at {})",
+        old_delay, new_delay, effective_at
+    );
+
+    Ok(())
+}
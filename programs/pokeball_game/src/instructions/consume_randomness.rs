@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, Transfer};
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
 use orao_solana_vrf::state::RandomnessAccountData;
 use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
 
@@ -7,6 +7,7 @@ use crate::state::*;
 use crate::errors::GameError;
 use crate::events::*;
 use crate::constants::*;
+use crate::instructions::session::restore_session_allowance;
 
 #[derive(Accounts)]
 pub struct ConsumeRandomness<'info> {
@@ -28,12 +29,22 @@ pub struct ConsumeRandomness<'info> {
     )]
     pub pokemon_slots: Box<Account<'info, PokemonSlots>>,
 
+    /// Closed on success; rent goes back to whoever paid for the request.
     #[account(
         mut,
         constraint = !vrf_request.is_fulfilled @ GameError::VrfAlreadyFulfilled,
+        close = request_payer,
     )]
     pub vrf_request: Account<'info, VrfRequest>,
 
+    /// Original payer of the VRF request account — receives its rent.
+    /// CHECK: Validated against vrf_request.payer.
+    #[account(
+        mut,
+        address = vrf_request.payer,
+    )]
+    pub request_payer: AccountInfo<'info>,
+
     /// ORAO VRF randomness account. Must match the seed in vrf_request.
     /// We manually deserialize RandomnessAccountData (enum) from the raw data.
     /// CHECK: Seeds are validated to ensure this is the correct ORAO randomness PDA.
@@ -53,21 +64,42 @@ pub struct ConsumeRandomness<'info> {
     pub nft_vault: Box<Account<'info, NftVault>>,
 
     /// Player inventory — needed for throw results to update stats.
-    /// Optional: only required for throw requests.
-    #[account(mut)]
+    /// Optional: only required for throw requests. Must be vrf_request.player's PDA.
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, vrf_request.player.as_ref()],
+        bump = player_inventory.bump,
+    )]
     pub player_inventory: Option<Account<'info, PlayerInventory>>,
 
     /// The player/winner wallet — needed as destination owner for NFT transfer.
-    /// CHECK: Validated against vrf_request.player in handler.
-    #[account(mut)]
+    /// CHECK: Validated against vrf_request.player.
+    #[account(
+        mut,
+        constraint = winner.key() == vrf_request.player @ GameError::WinnerMismatch,
+    )]
     pub winner: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// Prize record for this request. Only created (paid by `payer`) when a
+    /// throw catches a Pokemon and the vault has an NFT to award.
+    /// CHECK: Address validated by seeds; created manually in handle_throw.
+    #[account(
+        mut,
+        seeds = [PENDING_PRIZE_SEED, vrf_request.key().as_ref()],
+        bump,
+    )]
+    pub pending_prize: UncheckedAccount<'info>,
+
+    /// SessionKey that paid for a session throw, to restore its allowance.
+    /// Required when vrf_request.session is set.
+    /// CHECK: Address validated against vrf_request.session; deserialized in the handler.
+    #[account(
+        mut,
+        address = vrf_request.session,
+    )]
+    pub session: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
-    // remaining_accounts: groups of 3 per vault NFT:
-    //   [0] NFT mint (AccountInfo, read-only)
-    //   [1] Vault's ATA for this mint (AccountInfo, writable)
-    //   [2] Player's ATA for this mint (AccountInfo, writable)
 }
 
 pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'info>>) -> Result<()> {
@@ -98,6 +130,11 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'info
 /// Handle VRF result for a spawn request.
 /// Assigns a random position and creates the Pokemon in the target slot.
 fn handle_spawn<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'info>>, randomness: &[u8; 64]) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_SPAWNS),
+        GameError::SpawnsPaused
+    );
+
     let slot_idx = ctx.accounts.vrf_request.slot_index as usize;
     require!(slot_idx < MAX_POKEMON_SLOTS, GameError::InvalidSlotIndex);
 
@@ -105,14 +142,20 @@ fn handle_spawn<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
     let pos_x = u16::from_le_bytes([randomness[0], randomness[1]]) % (MAX_COORDINATE + 1);
     let pos_y = u16::from_le_bytes([randomness[2], randomness[3]]) % (MAX_COORDINATE + 1);
 
+    // Draw rarity from bytes [4..12] using the configured weights
+    let rarity_bytes: [u8; 8] = randomness[4..12].try_into().unwrap();
+    let rarity = ctx.accounts.game_config
+        .rarity_for_roll(u64::from_le_bytes(rarity_bytes) % BPS_DENOMINATOR);
+
     // Assign Pokemon ID
     ctx.accounts.game_config.pokemon_id_counter = ctx.accounts.game_config.pokemon_id_counter
         .checked_add(1)
         .ok_or(GameError::MathOverflow)?;
     let pokemon_id = ctx.accounts.game_config.pokemon_id_counter;
 
-    // Spawn the Pokemon
+    // Spawn the Pokemon and release the slot reservation
     let clock = Clock::get()?;
+    ctx.accounts.pokemon_slots.set_spawn_pending(slot_idx, false);
     ctx.accounts.pokemon_slots.slots[slot_idx] = PokemonSlot {
         is_active: true,
         pokemon_id,
@@ -120,6 +163,7 @@ fn handle_spawn<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
         pos_y,
         throw_attempts: 0,
         spawn_timestamp: clock.unix_timestamp,
+        rarity,
     };
     ctx.accounts.pokemon_slots.active_count = ctx.accounts.pokemon_slots.active_count
         .checked_add(1)
@@ -134,11 +178,12 @@ fn handle_spawn<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
         slot_index,
         pos_x,
         pos_y,
+        rarity,
     });
 
     msg!(
-        "VRF spawn complete: Pokemon {} at ({}, {}) in slot {}",
-        pokemon_id, pos_x, pos_y, slot_index
+        "VRF spawn complete: Pokemon {} (rarity {}) at ({}, {}) in slot {}",
+        pokemon_id, rarity, pos_x, pos_y, slot_index
     );
 
     Ok(())
@@ -147,13 +192,30 @@ fn handle_spawn<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
 /// Handle VRF result for a throw request.
 /// Determines catch/miss, awards NFT if caught and vault has stock.
 fn handle_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'info>>, randomness: &[u8; 64]) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_THROWS),
+        GameError::ThrowsPaused
+    );
+
     let slot_idx = ctx.accounts.vrf_request.slot_index as usize;
     require!(slot_idx < MAX_POKEMON_SLOTS, GameError::InvalidSlotIndex);
 
     let ball_type = ctx.accounts.vrf_request.ball_type as usize;
     require!(ball_type < NUM_BALL_TYPES, GameError::InvalidBallType);
 
-    let catch_rate = ctx.accounts.game_config.catch_rates[ball_type];
+    require!(
+        ctx.accounts.player_inventory.is_some(),
+        GameError::PlayerInventoryMissing
+    );
+
+    // The targeted Pokemon may have been caught, despawned or replaced since the throw.
+    let target = ctx.accounts.pokemon_slots.slots[slot_idx];
+    if !target.is_active || target.pokemon_id != ctx.accounts.vrf_request.pokemon_id {
+        return void_throw(ctx, target.pokemon_id);
+    }
+
+    // Rarer Pokemon are harder (or easier) to catch per rarity_catch_multipliers
+    let catch_rate = ctx.accounts.game_config.effective_catch_rate(ball_type, target.rarity);
 
     // Use bytes [0..8] for catch determination
     let catch_bytes: [u8; 8] = randomness[0..8].try_into().unwrap();
@@ -167,7 +229,6 @@ fn handle_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
     if caught {
         // === CAUGHT ===
         let mut awarded_mint = Pubkey::default();
-        let mut nft_transferred = false;
 
         if ctx.accounts.nft_vault.count > 0 {
             // Use bytes [8..16] for NFT selection (independent from catch roll)
@@ -176,9 +237,8 @@ fn handle_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
 
             awarded_mint = ctx.accounts.nft_vault.mints[nft_index];
 
-            // ALWAYS remove NFT from vault FIRST (prevents double-award).
-            // Even if remaining_accounts don't contain the right transfer accounts,
-            // the vault is updated atomically so no other catch can select this NFT.
+            // Remove NFT from vault FIRST (prevents double-award). The token stays in
+            // the vault ATA until the winner (or anyone) calls claim_prize.
             let last_idx = (ctx.accounts.nft_vault.count - 1) as usize;
             if nft_index != last_idx {
                 ctx.accounts.nft_vault.mints[nft_index] = ctx.accounts.nft_vault.mints[last_idx];
@@ -186,63 +246,26 @@ fn handle_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
             ctx.accounts.nft_vault.mints[last_idx] = Pubkey::default();
             ctx.accounts.nft_vault.count = ctx.accounts.nft_vault.count.saturating_sub(1);
 
-            // Search remaining_accounts for the awarded mint's transfer accounts.
-            // Layout: groups of 3 [nft_mint, vault_ata, player_ata].
-            // The frontend passes ALL vault NFTs; the program picks the winner here.
-            let remaining = &ctx.remaining_accounts;
-            let num_nft_groups = remaining.len() / 3;
-
-            for i in 0..num_nft_groups {
-                let ra_mint = &remaining[i * 3];
-                let ra_vault_ata = &remaining[i * 3 + 1];
-                let ra_player_ata = &remaining[i * 3 + 2];
-
-                if ra_mint.key() == awarded_mint {
-                    // Validate token account ownership (must be SPL Token program)
-                    require!(
-                        *ra_vault_ata.owner == token::ID,
-                        GameError::NftTransferAccountsMissing
-                    );
-                    require!(
-                        *ra_player_ata.owner == token::ID,
-                        GameError::NftTransferAccountsMissing
-                    );
-
-                    // Transfer 1 NFT from vault ATA to player ATA
-                    let nft_vault_seeds = &[
-                        NFT_VAULT_SEED,
-                        &[ctx.accounts.nft_vault.bump],
-                    ];
-                    let vault_signer_seeds = &[&nft_vault_seeds[..]];
-
-                    let transfer_ctx = CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ra_vault_ata.to_account_info(),
-                            to: ra_player_ata.to_account_info(),
-                            authority: ctx.accounts.nft_vault.to_account_info(),
-                        },
-                        vault_signer_seeds,
-                    );
-                    token::transfer(transfer_ctx, 1)?;
-
-                    nft_transferred = true;
-                    break;
-                }
-            }
+            // Record the prize on-chain so claim_prize can deliver it
+            create_pending_prize(&ctx, ctx.bumps.pending_prize)?;
+            let pending_prize = PendingPrize {
+                winner: player,
+                nft_mint: awarded_mint,
+                vrf_request: ctx.accounts.vrf_request.key(),
+                pokemon_id,
+                rent_payer: ctx.accounts.payer.key(),
+                created_at: Clock::get()?.unix_timestamp,
+                bump: ctx.bumps.pending_prize,
+            };
+            let mut data = ctx.accounts.pending_prize.try_borrow_mut_data()?;
+            pending_prize.try_serialize(&mut &mut data[..])?;
+            drop(data);
 
             emit!(NftAwarded {
                 winner: player,
                 nft_mint: awarded_mint,
                 vault_remaining: ctx.accounts.nft_vault.count,
             });
-
-            if !nft_transferred {
-                msg!(
-                    "WARNING: NFT {} removed from vault but transfer accounts not provided. Backend sweep required.",
-                    awarded_mint
-                );
-            }
         }
 
         // Update player stats
@@ -261,15 +284,15 @@ fn handle_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
             pokemon_id,
             slot_index,
             nft_mint: awarded_mint,
+            rarity: target.rarity,
         });
 
         msg!(
-            "Pokemon {} CAUGHT by {}! NFT: {} (transferred: {})",
+            "Pokemon {} CAUGHT by {}! NFT: {}",
             pokemon_id,
             player,
             if awarded_mint == Pubkey::default() { "none (vault empty)".to_string() }
-            else { awarded_mint.to_string() },
-            nft_transferred
+            else { format!("{} (claimable)", awarded_mint) }
         );
     } else {
         // === MISSED ===
@@ -341,3 +364,107 @@ fn handle_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'inf
 
     Ok(())
 }
+
+/// Resolve a throw whose target is gone: refund the ball and leave the slot untouched.
+fn void_throw<'info>(ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'info>>, current_pokemon_id: u64) -> Result<()> {
+    let player = ctx.accounts.vrf_request.player;
+    let pokemon_id = ctx.accounts.vrf_request.pokemon_id;
+    let slot_index = ctx.accounts.vrf_request.slot_index;
+    let ball_type = ctx.accounts.vrf_request.ball_type;
+
+    let inventory = ctx.accounts.player_inventory
+        .as_mut()
+        .ok_or(GameError::PlayerInventoryMissing)?;
+    inventory.balls[ball_type as usize] = inventory.balls[ball_type as usize]
+        .checked_add(1)
+        .ok_or(GameError::MathOverflow)?;
+    inventory.total_throws = inventory.total_throws.saturating_sub(1);
+
+    restore_session_allowance(
+        ctx.accounts.session.as_ref().map(|s| s.to_account_info()),
+        &ctx.accounts.vrf_request,
+    )?;
+
+    // Mark VRF request fulfilled
+    ctx.accounts.vrf_request.is_fulfilled = true;
+
+    emit!(ThrowVoided {
+        thrower: player,
+        pokemon_id,
+        slot_index,
+        ball_type,
+        current_pokemon_id,
+    });
+
+    msg!(
+        "Throw at Pokemon {} voided: slot {} now holds {}. Ball refunded to {}",
+        pokemon_id, slot_index, current_pokemon_id, player
+    );
+
+    Ok(())
+}
+
+/// Create the PendingPrize PDA, paid by the cranker. Tolerates lamports
+/// pre-sent to the address (create_account would reject a funded account).
+fn create_pending_prize<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ConsumeRandomness<'info>>,
+    bump: u8,
+) -> Result<()> {
+    let prize_info = ctx.accounts.pending_prize.to_account_info();
+    require!(prize_info.data_is_empty(), GameError::PendingPrizeInUse);
+
+    let vrf_request_key = ctx.accounts.vrf_request.key();
+    let prize_seeds = &[
+        PENDING_PRIZE_SEED,
+        vrf_request_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&prize_seeds[..]];
+
+    let system = ctx.accounts.system_program.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let rent = Rent::get()?.minimum_balance(PendingPrize::LEN);
+    let current = prize_info.lamports();
+
+    if current == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system,
+                CreateAccount { from: payer, to: prize_info },
+                signer_seeds,
+            ),
+            rent,
+            PendingPrize::LEN as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let top_up = rent.saturating_sub(current);
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system.clone(),
+                    Transfer { from: payer, to: prize_info.clone() },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system.clone(),
+                Allocate { account_to_allocate: prize_info.clone() },
+                signer_seeds,
+            ),
+            PendingPrize::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system,
+                Assign { account_to_assign: prize_info },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    Ok(())
+}
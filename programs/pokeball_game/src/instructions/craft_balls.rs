@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::BallsCrafted;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CraftBalls<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump = player_inventory.bump,
+        constraint = player_inventory.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+}
+
+pub fn handler(
+    ctx: Context<CraftBalls>,
+    from_ball_type: u8,
+    count: u32,
+) -> Result<()> {
+    let game_config = &ctx.accounts.game_config;
+    require!(
+        !game_config.is_paused(PAUSE_CRAFTING),
+        GameError::CraftingPaused
+    );
+
+    // Master Balls are the top tier
+    require!(
+        (from_ball_type as usize) < NUM_CRAFT_RECIPES,
+        GameError::CraftNotAvailable
+    );
+    let ratio = game_config.craft_ratios[from_ball_type as usize];
+    require!(ratio > 0, GameError::CraftNotAvailable);
+
+    require!(count > 0, GameError::ZeroQuantity);
+
+    let consumed = ratio
+        .checked_mul(count)
+        .ok_or(GameError::MathOverflow)?;
+    let to_ball_type = from_ball_type + 1;
+
+    let inventory = &mut ctx.accounts.player_inventory;
+    inventory.debit_balls(&mut ctx.accounts.game_config, from_ball_type, consumed)?;
+    inventory.balls[to_ball_type as usize] = inventory.balls[to_ball_type as usize]
+        .checked_add(count)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(BallsCrafted {
+        player: ctx.accounts.player.key(),
+        from_ball_type,
+        to_ball_type,
+        consumed,
+        produced: count,
+    });
+
+    msg!(
+        "Player {} crafted {} balls of type {} from {} balls of type {}",
+        ctx.accounts.player.key(),
+        count,
+        to_ball_type,
+        consumed,
+        from_ball_type
+    );
+
+    Ok(())
+}
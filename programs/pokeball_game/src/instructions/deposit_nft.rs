@@ -16,7 +16,7 @@ pub struct DepositNft<'info> {
         seeds = [GAME_CONFIG_SEED],
         bump = game_config.bump,
         constraint = game_config.is_initialized @ GameError::NotInitialized,
-        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+        constraint = game_config.has_role(ROLE_VAULT_MANAGER, &authority.key()) @ GameError::Unauthorized,
     )]
     pub game_config: Box<Account<'info, GameConfig>>,
 
@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetRevenueSplit<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeRevenue<'info> {
+    /// Anyone can distribute. Pays rent for the bucket accounts on first use.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+
+    #[account(address = game_config.solballs_mint)]
+    pub solballs_mint: Account<'info, Mint>,
+
+    /// Game's SolBalls token account (source, PDA-owned).
+    #[account(
+        mut,
+        constraint = game_solballs_account.owner == game_config.key(),
+        constraint = game_solballs_account.mint == game_config.solballs_mint,
+    )]
+    pub game_solballs_account: Account<'info, TokenAccount>,
+
+    /// Treasury wallet's SolBalls token account.
+    #[account(
+        mut,
+        constraint = treasury_solballs_account.owner == treasury_config.treasury_wallet @ GameError::InvalidTreasuryAccount,
+        constraint = treasury_solballs_account.mint == game_config.solballs_mint @ GameError::InvalidTreasuryAccount,
+    )]
+    pub treasury_solballs_account: Account<'info, TokenAccount>,
+
+    /// Prize pool SolBalls account (PDA, owned by game_config).
+    #[account(
+        init_if_needed,
+        payer = caller,
+        seeds = [PRIZE_POOL_SEED],
+        bump,
+        token::mint = solballs_mint,
+        token::authority = game_config,
+    )]
+    pub prize_pool: Account<'info, TokenAccount>,
+
+    /// Reserve SolBalls account (PDA, owned by game_config).
+    #[account(
+        init_if_needed,
+        payer = caller,
+        seeds = [RESERVE_SEED],
+        bump,
+        token::mint = solballs_mint,
+        token::authority = game_config,
+    )]
+    pub reserve: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_split_handler(
+    ctx: Context<SetRevenueSplit>,
+    split_bps: [u16; NUM_SPLIT_BUCKETS],
+) -> Result<()> {
+    TreasuryConfig::validate_split(&split_bps)?;
+
+    let treasury_config = &mut ctx.accounts.treasury_config;
+    let old_split_bps = treasury_config.split_bps;
+    treasury_config.split_bps = split_bps;
+
+    emit!(RevenueSplitUpdated {
+        old_split_bps,
+        new_split_bps: split_bps,
+    });
+
+    msg!("Revenue split updated: {:?} -> {:?}", old_split_bps, split_bps);
+
+    Ok(())
+}
+
+/// Accounts shared by every path that moves revenue out of the game SolBalls account.
+pub(crate) struct RevenueSplit<'a, 'info> {
+    pub game_config: &'a Account<'info, GameConfig>,
+    pub treasury_config: &'a mut Account<'info, TreasuryConfig>,
+    pub game_solballs_account: AccountInfo<'info>,
+    pub treasury_solballs_account: AccountInfo<'info>,
+    pub prize_pool: AccountInfo<'info>,
+    pub reserve: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<DistributeRevenue>) -> Result<()> {
+    // SolBalls still refundable through sell_back_balls stay in the game account
+    let balance = ctx.accounts.game_config.distributable(ctx.accounts.game_solballs_account.amount);
+    require!(balance > 0, GameError::NothingToDistribute);
+
+    let accounts = ctx.accounts;
+    split_revenue(
+        RevenueSplit {
+            game_config: &accounts.game_config,
+            treasury_config: &mut accounts.treasury_config,
+            game_solballs_account: accounts.game_solballs_account.to_account_info(),
+            treasury_solballs_account: accounts.treasury_solballs_account.to_account_info(),
+            prize_pool: accounts.prize_pool.to_account_info(),
+            reserve: accounts.reserve.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        },
+        balance,
+    )?;
+
+    Ok(())
+}
+
+/// Move `amount` SolBalls from the game account into the treasury wallet,
+/// prize pool and reserve per `TreasuryConfig.split_bps`. Returns each
+/// bucket's share, indexed by SPLIT_*.
+pub(crate) fn split_revenue(
+    split: RevenueSplit,
+    amount: u64,
+) -> Result<[u64; NUM_SPLIT_BUCKETS]> {
+    // Treasury and reserve round down; the prize pool takes the remainder
+    let split_bps = split.treasury_config.split_bps;
+    let share = |bucket: usize| -> Result<u64> {
+        let share = (amount as u128)
+            .checked_mul(split_bps[bucket] as u128)
+            .ok_or(GameError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(share as u64)
+    };
+    let to_treasury = share(SPLIT_TREASURY)?;
+    let to_reserve = share(SPLIT_RESERVE)?;
+    let to_prize_pool = amount
+        .checked_sub(to_treasury)
+        .and_then(|rest| rest.checked_sub(to_reserve))
+        .ok_or(GameError::MathOverflow)?;
+
+    let config_seeds = &[
+        GAME_CONFIG_SEED,
+        &[split.game_config.bump],
+    ];
+    let signer_seeds = &[&config_seeds[..]];
+
+    for (destination, share) in [
+        (split.treasury_solballs_account.clone(), to_treasury),
+        (split.prize_pool.clone(), to_prize_pool),
+        (split.reserve.clone(), to_reserve),
+    ] {
+        if share == 0 {
+            continue;
+        }
+        let transfer_ctx = CpiContext::new_with_signer(
+            split.token_program.clone(),
+            Transfer {
+                from: split.game_solballs_account.clone(),
+                to: destination,
+                authority: split.game_config.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, share)?;
+    }
+
+    let treasury_config = split.treasury_config;
+    treasury_config.total_to_treasury = treasury_config.total_to_treasury
+        .checked_add(to_treasury)
+        .ok_or(GameError::MathOverflow)?;
+    treasury_config.total_to_prize_pool = treasury_config.total_to_prize_pool
+        .checked_add(to_prize_pool)
+        .ok_or(GameError::MathOverflow)?;
+    treasury_config.total_to_reserve = treasury_config.total_to_reserve
+        .checked_add(to_reserve)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(TreasuryRevenueDistributed {
+        treasury_wallet: treasury_config.treasury_wallet,
+        amount: to_treasury,
+        total_to_treasury: treasury_config.total_to_treasury,
+    });
+    emit!(PrizePoolRevenueDistributed {
+        prize_pool: split.prize_pool.key(),
+        amount: to_prize_pool,
+        total_to_prize_pool: treasury_config.total_to_prize_pool,
+    });
+    emit!(ReserveRevenueDistributed {
+        reserve: split.reserve.key(),
+        amount: to_reserve,
+        total_to_reserve: treasury_config.total_to_reserve,
+    });
+
+    msg!(
+        "Distributed {} SolBalls: treasury {}, prize pool {}, reserve {}",
+        amount, to_treasury, to_prize_pool, to_reserve
+    );
+
+    let mut shares = [0; NUM_SPLIT_BUCKETS];
+    shares[SPLIT_TREASURY] = to_treasury;
+    shares[SPLIT_PRIZE_POOL] = to_prize_pool;
+    shares[SPLIT_RESERVE] = to_reserve;
+    Ok(shares)
+}
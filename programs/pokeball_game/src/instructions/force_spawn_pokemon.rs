@@ -15,7 +15,7 @@ pub struct ForceSpawnPokemon<'info> {
         seeds = [GAME_CONFIG_SEED],
         bump = game_config.bump,
         constraint = game_config.is_initialized @ GameError::NotInitialized,
-        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+        constraint = game_config.has_role(ROLE_SPAWNER, &authority.key()) @ GameError::Unauthorized,
     )]
     pub game_config: Box<Account<'info, GameConfig>>,
 
@@ -32,7 +32,13 @@ pub fn handler(
     slot_index: u8,
     pos_x: u16,
     pos_y: u16,
+    rarity: u8,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_SPAWNS),
+        GameError::SpawnsPaused
+    );
+
     let slot_idx = slot_index as usize;
 
     // Validate slot index
@@ -42,18 +48,25 @@ pub fn handler(
     require!(pos_x <= MAX_COORDINATE, GameError::InvalidCoordinate);
     require!(pos_y <= MAX_COORDINATE, GameError::InvalidCoordinate);
 
+    // Validate rarity
+    require!((rarity as usize) < NUM_RARITIES, GameError::InvalidRarity);
+
     let pokemon_slots = &ctx.accounts.pokemon_slots;
 
-    // Check slot is empty
+    // Check slot is empty and not reserved by a pending VRF spawn
     require!(
         !pokemon_slots.slots[slot_idx].is_active,
         GameError::SlotAlreadyOccupied
     );
+    require!(
+        !pokemon_slots.is_spawn_pending(slot_idx),
+        GameError::SlotSpawnPending
+    );
 
-    // Check max active count
+    // Check max active count (pending spawns count towards the cap)
     let game_config = &ctx.accounts.game_config;
     require!(
-        pokemon_slots.active_count < game_config.max_active_pokemon,
+        pokemon_slots.occupied_count() < game_config.max_active_pokemon as u32,
         GameError::MaxActivePokemonReached
     );
 
@@ -75,6 +88,7 @@ pub fn handler(
         pos_y,
         throw_attempts: 0,
         spawn_timestamp: clock.unix_timestamp,
+        rarity,
     };
     pokemon_slots.active_count = pokemon_slots.active_count
         .checked_add(1)
@@ -85,11 +99,12 @@ pub fn handler(
         slot_index,
         pos_x,
         pos_y,
+        rarity,
     });
 
     msg!(
-        "Force spawned Pokemon {} at slot {} ({}, {})",
-        pokemon_id, slot_index, pos_x, pos_y
+        "Force spawned Pokemon {} (rarity {}) at slot {} ({}, {})",
+        pokemon_id, rarity, slot_index, pos_x, pos_y
     );
 
     Ok(())
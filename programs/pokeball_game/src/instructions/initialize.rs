@@ -5,6 +5,7 @@ use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::errors::GameError;
 use crate::constants::*;
+use crate::instructions::purchase_balls_with_sol::fund_sol_treasury;
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -47,6 +48,14 @@ pub struct Initialize<'info> {
     )]
     pub treasury_config: Account<'info, TreasuryConfig>,
 
+    /// Game's SOL treasury (system-owned PDA). Funded to the rent-exempt minimum.
+    #[account(
+        mut,
+        seeds = [SOL_TREASURY_SEED],
+        bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
     /// The SolBalls token mint.
     pub solballs_mint: Account<'info, Mint>,
 
@@ -93,7 +102,8 @@ pub fn handler(
     // Initialize GameConfig
     let game_config = &mut ctx.accounts.game_config;
     game_config.authority = ctx.accounts.authority.key();
-    game_config.treasury = treasury;
+    game_config.pending_authority = Pubkey::default();
+    game_config.roles = [Pubkey::default(); NUM_ROLES];
     game_config.solballs_mint = solballs_mint;
     game_config.usdc_mint = usdc_mint;
     game_config.ball_prices = ball_prices;
@@ -103,12 +113,36 @@ pub fn handler(
     game_config.total_revenue = 0;
     game_config.is_initialized = true;
     game_config.vrf_counter = 0;
+    game_config.pause_flags = 0;
+    game_config.config_change_delay = DEFAULT_CONFIG_CHANGE_DELAY;
+    game_config.vrf_timeout = DEFAULT_VRF_TIMEOUT;
+    game_config.relayer = Pubkey::default();
+    game_config.usdc_ball_prices = [0; NUM_BALL_TYPES];
+    game_config.total_usdc_revenue = 0;
+    game_config.sol_ball_prices = [0; NUM_BALL_TYPES];
+    game_config.total_sol_revenue = 0;
+    game_config.usd_ball_prices = [0; NUM_BALL_TYPES];
+    game_config.price_feed = Pubkey::default();
+    game_config.max_price_age = DEFAULT_MAX_PRICE_AGE;
+    game_config.max_price_conf_bps = DEFAULT_MAX_PRICE_CONF_BPS;
+    game_config.daily_spend_limits = [0; NUM_SPEND_CURRENCIES];
+    game_config.weekly_spend_limits = [0; NUM_SPEND_CURRENCIES];
+    game_config.ball_mints = [Pubkey::default(); NUM_BALL_TYPES];
+    game_config.craft_ratios = DEFAULT_CRAFT_RATIOS;
+    game_config.refund_window = DEFAULT_REFUND_WINDOW;
+    game_config.refund_haircut_bps = DEFAULT_REFUND_HAIRCUT_BPS;
+    game_config.rarity_weights = DEFAULT_RARITY_WEIGHTS;
+    game_config.rarity_catch_multipliers = DEFAULT_RARITY_CATCH_MULTIPLIERS;
+    game_config.config_change_delay_floor = 0;
+    game_config.config_change_delay_floor_until = 0;
+    game_config.outstanding_refundable = 0;
     game_config.bump = ctx.bumps.game_config;
 
     // Initialize PokemonSlots
     let pokemon_slots = &mut ctx.accounts.pokemon_slots;
     pokemon_slots.slots = [PokemonSlot::default(); MAX_POKEMON_SLOTS];
     pokemon_slots.active_count = 0;
+    pokemon_slots.pending_spawn_mask = 0;
     pokemon_slots.bump = ctx.bumps.pokemon_slots;
 
     // Initialize NftVault
@@ -123,8 +157,22 @@ pub fn handler(
     let treasury_config = &mut ctx.accounts.treasury_config;
     treasury_config.treasury_wallet = treasury;
     treasury_config.total_withdrawn = 0;
+    treasury_config.total_usdc_withdrawn = 0;
+    treasury_config.total_sol_withdrawn = 0;
+    treasury_config.split_bps = DEFAULT_SPLIT_BPS;
+    treasury_config.total_to_treasury = 0;
+    treasury_config.total_to_prize_pool = 0;
+    treasury_config.total_to_reserve = 0;
+    treasury_config.total_from_prize_pool = 0;
+    treasury_config.total_from_reserve = 0;
     treasury_config.bump = ctx.bumps.treasury_config;
 
+    fund_sol_treasury(
+        &ctx.accounts.sol_treasury.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
     msg!("Game initialized. Authority: {}", ctx.accounts.authority.key());
     Ok(())
 }
@@ -0,0 +1,572 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::AccountMigrated;
+use crate::constants::*;
+use crate::instructions::purchase_balls_with_sol::fund_sol_treasury;
+
+/// GameConfig layout at launch, before any fields were appended. The treasury
+/// copy was dropped; TreasuryConfig.treasury_wallet already holds it.
+#[derive(AnchorDeserialize)]
+struct LegacyGameConfig {
+    authority: Pubkey,
+    _treasury: Pubkey,
+    solballs_mint: Pubkey,
+    usdc_mint: Pubkey,
+    ball_prices: [u64; 4],
+    catch_rates: [u8; 4],
+    max_active_pokemon: u8,
+    pokemon_id_counter: u64,
+    total_revenue: u64,
+    is_initialized: bool,
+    vrf_counter: u64,
+    bump: u8,
+}
+
+impl LegacyGameConfig {
+    const LEN: usize = 8  // discriminator
+        + 32  // authority
+        + 32  // treasury
+        + 32  // solballs_mint
+        + 32  // usdc_mint
+        + (8 * 4)  // ball_prices
+        + 4   // catch_rates
+        + 1   // max_active_pokemon
+        + 8   // pokemon_id_counter
+        + 8   // total_revenue
+        + 1   // is_initialized
+        + 8   // vrf_counter
+        + 1;  // bump
+}
+
+/// TreasuryConfig layout at launch.
+#[derive(AnchorDeserialize)]
+struct LegacyTreasuryConfig {
+    treasury_wallet: Pubkey,
+    total_withdrawn: u64,
+    bump: u8,
+}
+
+impl LegacyTreasuryConfig {
+    const LEN: usize = 8  // discriminator
+        + 32  // treasury_wallet
+        + 8   // total_withdrawn
+        + 1;  // bump
+}
+
+/// PlayerInventory layout at launch.
+#[derive(AnchorDeserialize)]
+struct LegacyPlayerInventory {
+    player: Pubkey,
+    balls: [u32; 4],
+    total_purchased: u64,
+    total_throws: u64,
+    total_catches: u64,
+    bump: u8,
+}
+
+impl LegacyPlayerInventory {
+    const LEN: usize = 8  // discriminator
+        + 32  // player
+        + (4 * 4)  // balls
+        + 8   // total_purchased
+        + 8   // total_throws
+        + 8   // total_catches
+        + 1;  // bump
+}
+
+/// VrfRequest layout at launch, before `payer`, `pokemon_id` and `requested_at`.
+/// Those requests were always paid for by `player` (the thrower, or the authority for spawns).
+#[derive(AnchorDeserialize)]
+pub(crate) struct LegacyVrfRequest {
+    pub request_type: u8,
+    pub player: Pubkey,
+    pub slot_index: u8,
+    pub ball_type: u8,
+    pub seed: [u8; 32],
+    pub is_fulfilled: bool,
+    pub bump: u8,
+}
+
+impl LegacyVrfRequest {
+    pub const LEN: usize = 8  // discriminator
+        + 1   // request_type
+        + 32  // player
+        + 1   // slot_index
+        + 1   // ball_type
+        + 32  // seed
+        + 1   // is_fulfilled
+        + 1;  // bump
+}
+
+/// PokemonSlot layout before `rarity` was added.
+#[derive(AnchorDeserialize, Clone, Copy)]
+struct LegacyPokemonSlot {
+    is_active: bool,
+    pokemon_id: u64,
+    pos_x: u16,
+    pos_y: u16,
+    throw_attempts: u8,
+    spawn_timestamp: i64,
+}
+
+impl LegacyPokemonSlot {
+    const LEN: usize = 1 + 8 + 2 + 2 + 1 + 8;
+}
+
+/// PokemonSlots layout at launch, before `pending_spawn_mask` and per-slot `rarity`.
+#[derive(AnchorDeserialize)]
+struct LegacyPokemonSlots {
+    slots: [LegacyPokemonSlot; MAX_POKEMON_SLOTS],
+    active_count: u8,
+    bump: u8,
+}
+
+impl LegacyPokemonSlots {
+    const LEN: usize = 8  // discriminator
+        + (LegacyPokemonSlot::LEN * MAX_POKEMON_SLOTS)  // slots
+        + 1   // active_count
+        + 1;  // bump
+}
+
+/// PokemonSlots layout with `pending_spawn_mask` but before per-slot `rarity`.
+#[derive(AnchorDeserialize)]
+struct LegacyMaskedPokemonSlots {
+    slots: [LegacyPokemonSlot; MAX_POKEMON_SLOTS],
+    active_count: u8,
+    pending_spawn_mask: u32,
+    bump: u8,
+}
+
+impl LegacyMaskedPokemonSlots {
+    const LEN: usize = 8  // discriminator
+        + (LegacyPokemonSlot::LEN * MAX_POKEMON_SLOTS)  // slots
+        + 1   // active_count
+        + 4   // pending_spawn_mask
+        + 1;  // bump
+}
+
+/// Accounts for growing GameConfig and TreasuryConfig to the current layout.
+/// Requires the authority recorded in the legacy GameConfig.
+#[derive(Accounts)]
+pub struct MigrateGameConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Legacy-layout GameConfig PDA. Deserialized manually because
+    /// `Account<GameConfig>` cannot read the old layout.
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub game_config: UncheckedAccount<'info>,
+
+    /// CHECK: Legacy-layout TreasuryConfig PDA. Skipped if already migrated.
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub treasury_config: UncheckedAccount<'info>,
+
+    /// Game's SOL treasury (system-owned PDA). Topped up to the rent-exempt minimum.
+    #[account(
+        mut,
+        seeds = [SOL_TREASURY_SEED],
+        bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for growing a player's inventory to the current layout. Anyone can pay.
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct MigratePlayerInventory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy-layout PlayerInventory PDA, deserialized manually.
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub player_inventory: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for growing a pending legacy VRF request so it can be consumed
+/// or cancelled. Anyone can pay.
+#[derive(Accounts)]
+pub struct MigrateVrfRequest<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy-layout VrfRequest, deserialized manually. Ownership and
+    /// discriminator are checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub vrf_request: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for growing PokemonSlots to the current layout. Requires the authority
+/// recorded in GameConfig, which may itself still be on the launch layout.
+#[derive(Accounts)]
+pub struct MigratePokemonSlots<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: GameConfig PDA on either layout; only the authority is read.
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub game_config: UncheckedAccount<'info>,
+
+    /// CHECK: Legacy-layout PokemonSlots PDA, deserialized manually.
+    #[account(
+        mut,
+        seeds = [POKEMON_SLOTS_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub pokemon_slots: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn game_config_handler(ctx: Context<MigrateGameConfig>) -> Result<()> {
+    let config_info = ctx.accounts.game_config.to_account_info();
+    let legacy: LegacyGameConfig = read_legacy(
+        &config_info,
+        GameConfig::DISCRIMINATOR,
+        LegacyGameConfig::LEN,
+        GameConfig::LEN,
+    )?;
+    require!(
+        legacy.authority == ctx.accounts.authority.key(),
+        GameError::Unauthorized
+    );
+
+    // Launch fields carry over; appended fields get the same defaults as initialize
+    let migrated = GameConfig {
+        authority: legacy.authority,
+        solballs_mint: legacy.solballs_mint,
+        usdc_mint: legacy.usdc_mint,
+        ball_prices: legacy.ball_prices,
+        catch_rates: legacy.catch_rates,
+        max_active_pokemon: legacy.max_active_pokemon,
+        pokemon_id_counter: legacy.pokemon_id_counter,
+        total_revenue: legacy.total_revenue,
+        is_initialized: legacy.is_initialized,
+        vrf_counter: legacy.vrf_counter,
+        pause_flags: 0,
+        pending_authority: Pubkey::default(),
+        roles: [Pubkey::default(); NUM_ROLES],
+        config_change_delay: DEFAULT_CONFIG_CHANGE_DELAY,
+        vrf_timeout: DEFAULT_VRF_TIMEOUT,
+        relayer: Pubkey::default(),
+        usdc_ball_prices: [0; NUM_BALL_TYPES],
+        total_usdc_revenue: 0,
+        sol_ball_prices: [0; NUM_BALL_TYPES],
+        total_sol_revenue: 0,
+        usd_ball_prices: [0; NUM_BALL_TYPES],
+        price_feed: Pubkey::default(),
+        max_price_age: DEFAULT_MAX_PRICE_AGE,
+        max_price_conf_bps: DEFAULT_MAX_PRICE_CONF_BPS,
+        daily_spend_limits: [0; NUM_SPEND_CURRENCIES],
+        weekly_spend_limits: [0; NUM_SPEND_CURRENCIES],
+        ball_mints: [Pubkey::default(); NUM_BALL_TYPES],
+        craft_ratios: DEFAULT_CRAFT_RATIOS,
+        refund_window: DEFAULT_REFUND_WINDOW,
+        refund_haircut_bps: DEFAULT_REFUND_HAIRCUT_BPS,
+        rarity_weights: DEFAULT_RARITY_WEIGHTS,
+        rarity_catch_multipliers: DEFAULT_RARITY_CATCH_MULTIPLIERS,
+        config_change_delay_floor: 0,
+        config_change_delay_floor_until: 0,
+        outstanding_refundable: 0,
+        bump: legacy.bump,
+    };
+    grow_and_write(
+        &config_info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        GameConfig::LEN,
+        &migrated,
+    )?;
+
+    emit!(AccountMigrated {
+        account: config_info.key(),
+        old_len: LegacyGameConfig::LEN as u64,
+        new_len: GameConfig::LEN as u64,
+    });
+
+    // TreasuryConfig grew alongside GameConfig
+    let treasury_info = ctx.accounts.treasury_config.to_account_info();
+    if treasury_info.data_len() != TreasuryConfig::LEN {
+        let legacy: LegacyTreasuryConfig = read_legacy(
+            &treasury_info,
+            TreasuryConfig::DISCRIMINATOR,
+            LegacyTreasuryConfig::LEN,
+            TreasuryConfig::LEN,
+        )?;
+        let migrated = TreasuryConfig {
+            treasury_wallet: legacy.treasury_wallet,
+            total_withdrawn: legacy.total_withdrawn,
+            total_usdc_withdrawn: 0,
+            total_sol_withdrawn: 0,
+            split_bps: DEFAULT_SPLIT_BPS,
+            total_to_treasury: 0,
+            total_to_prize_pool: 0,
+            total_to_reserve: 0,
+            total_from_prize_pool: 0,
+            total_from_reserve: 0,
+            bump: legacy.bump,
+        };
+        grow_and_write(
+            &treasury_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            TreasuryConfig::LEN,
+            &migrated,
+        )?;
+
+        emit!(AccountMigrated {
+            account: treasury_info.key(),
+            old_len: LegacyTreasuryConfig::LEN as u64,
+            new_len: TreasuryConfig::LEN as u64,
+        });
+    }
+
+    // SOL purchases did not exist at launch, so the treasury may be unfunded
+    fund_sol_treasury(
+        &ctx.accounts.sol_treasury.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!("GameConfig migrated: {} -> {} bytes", LegacyGameConfig::LEN, GameConfig::LEN);
+
+    Ok(())
+}
+
+pub fn player_inventory_handler(
+    ctx: Context<MigratePlayerInventory>,
+    player: Pubkey,
+) -> Result<()> {
+    let inventory_info = ctx.accounts.player_inventory.to_account_info();
+    let legacy: LegacyPlayerInventory = read_legacy(
+        &inventory_info,
+        PlayerInventory::DISCRIMINATOR,
+        LegacyPlayerInventory::LEN,
+        PlayerInventory::LEN,
+    )?;
+    require!(legacy.player == player, GameError::InvalidPlayerInventory);
+
+    let migrated = PlayerInventory {
+        player: legacy.player,
+        balls: legacy.balls,
+        total_purchased: legacy.total_purchased,
+        total_throws: legacy.total_throws,
+        total_catches: legacy.total_catches,
+        relay_nonce: 0,
+        refund_window_start: 0,
+        refundable_balls: [0; NUM_BALL_TYPES],
+        refundable_cost: [0; NUM_BALL_TYPES],
+        bump: legacy.bump,
+    };
+    grow_and_write(
+        &inventory_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        PlayerInventory::LEN,
+        &migrated,
+    )?;
+
+    emit!(AccountMigrated {
+        account: inventory_info.key(),
+        old_len: LegacyPlayerInventory::LEN as u64,
+        new_len: PlayerInventory::LEN as u64,
+    });
+
+    msg!("PlayerInventory for {} migrated", player);
+
+    Ok(())
+}
+
+pub fn vrf_request_handler(ctx: Context<MigrateVrfRequest>) -> Result<()> {
+    let request_info = ctx.accounts.vrf_request.to_account_info();
+    let legacy: LegacyVrfRequest = read_legacy(
+        &request_info,
+        VrfRequest::DISCRIMINATOR,
+        LegacyVrfRequest::LEN,
+        VrfRequest::LEN,
+    )?;
+    // Fulfilled legacy requests are reclaimed by close_fulfilled_requests instead
+    require!(!legacy.is_fulfilled, GameError::VrfAlreadyFulfilled);
+
+    // The target Pokemon was never recorded, so a migrated throw resolves as
+    // voided (ball refunded). The timeout starts from the migration.
+    let migrated = VrfRequest {
+        request_type: legacy.request_type,
+        player: legacy.player,
+        slot_index: legacy.slot_index,
+        ball_type: legacy.ball_type,
+        seed: legacy.seed,
+        is_fulfilled: false,
+        payer: legacy.player,
+        pokemon_id: 0,
+        requested_at: Clock::get()?.unix_timestamp,
+        session: Pubkey::default(),
+        bump: legacy.bump,
+    };
+    grow_and_write(
+        &request_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        VrfRequest::LEN,
+        &migrated,
+    )?;
+
+    emit!(AccountMigrated {
+        account: request_info.key(),
+        old_len: LegacyVrfRequest::LEN as u64,
+        new_len: VrfRequest::LEN as u64,
+    });
+
+    msg!("VrfRequest {} migrated", request_info.key());
+
+    Ok(())
+}
+
+pub fn pokemon_slots_handler(ctx: Context<MigratePokemonSlots>) -> Result<()> {
+    require!(
+        config_authority(&ctx.accounts.game_config)? == ctx.accounts.authority.key(),
+        GameError::Unauthorized
+    );
+
+    // Launch accounts have no pending_spawn_mask; no spawns can be in flight for them
+    let slots_info = ctx.accounts.pokemon_slots.to_account_info();
+    let old_len = slots_info.data_len();
+    let (legacy_slots, active_count, pending_spawn_mask, bump) =
+        if old_len == LegacyMaskedPokemonSlots::LEN {
+            let legacy: LegacyMaskedPokemonSlots = read_legacy(
+                &slots_info,
+                PokemonSlots::DISCRIMINATOR,
+                LegacyMaskedPokemonSlots::LEN,
+                PokemonSlots::LEN,
+            )?;
+            (legacy.slots, legacy.active_count, legacy.pending_spawn_mask, legacy.bump)
+        } else {
+            let legacy: LegacyPokemonSlots = read_legacy(
+                &slots_info,
+                PokemonSlots::DISCRIMINATOR,
+                LegacyPokemonSlots::LEN,
+                PokemonSlots::LEN,
+            )?;
+            (legacy.slots, legacy.active_count, 0, legacy.bump)
+        };
+
+    // Existing Pokemon become common
+    let mut migrated = PokemonSlots {
+        slots: [PokemonSlot::default(); MAX_POKEMON_SLOTS],
+        active_count,
+        pending_spawn_mask,
+        bump,
+    };
+    for (slot, old) in migrated.slots.iter_mut().zip(legacy_slots.iter()) {
+        *slot = PokemonSlot {
+            is_active: old.is_active,
+            pokemon_id: old.pokemon_id,
+            pos_x: old.pos_x,
+            pos_y: old.pos_y,
+            throw_attempts: old.throw_attempts,
+            spawn_timestamp: old.spawn_timestamp,
+            rarity: RARITY_COMMON,
+        };
+    }
+    grow_and_write(
+        &slots_info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        PokemonSlots::LEN,
+        &migrated,
+    )?;
+
+    emit!(AccountMigrated {
+        account: slots_info.key(),
+        old_len: old_len as u64,
+        new_len: PokemonSlots::LEN as u64,
+    });
+
+    msg!("PokemonSlots migrated: {} -> {} bytes", old_len, PokemonSlots::LEN);
+
+    Ok(())
+}
+
+/// Authority stored in a GameConfig on either layout (it is the first field in both).
+fn config_authority(info: &AccountInfo) -> Result<Pubkey> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 + 32 && data.starts_with(GameConfig::DISCRIMINATOR),
+        GameError::InvalidAccountLayout
+    );
+    Pubkey::deserialize(&mut &data[8..40]).map_err(|_| GameError::InvalidAccountLayout.into())
+}
+
+/// Deserialize a launch-layout account, rejecting accounts already on the current layout.
+fn read_legacy<T: AnchorDeserialize>(
+    info: &AccountInfo,
+    discriminator: &[u8],
+    legacy_len: usize,
+    current_len: usize,
+) -> Result<T> {
+    let data = info.try_borrow_data()?;
+    require!(data.len() != current_len, GameError::AccountAlreadyMigrated);
+    require!(
+        data.len() == legacy_len && data.starts_with(discriminator),
+        GameError::InvalidAccountLayout
+    );
+    T::deserialize(&mut &data[8..]).map_err(|_| GameError::InvalidAccountLayout.into())
+}
+
+/// Top up rent from `payer`, resize `info` to `new_len` and write `value` into it.
+pub(crate) fn grow_and_write<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+    value: &T,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(info.lamports());
+    if shortfall > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: info.clone(),
+            },
+        );
+        system_program::transfer(transfer_ctx, shortfall)?;
+    }
+
+    info.resize(new_len)?;
+    let mut data = info.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
@@ -1,25 +1,62 @@
 pub mod initialize;
 pub mod purchase_balls;
+pub mod purchase_balls_with_usdc;
+pub mod purchase_balls_with_sol;
+pub mod purchase_balls_at_oracle_price;
+pub mod purchase_ball_bundle;
+pub mod purchase_balls_for;
+pub mod self_exclude;
 pub mod spawn_pokemon;
 pub mod force_spawn_pokemon;
 pub mod reposition_pokemon;
 pub mod despawn_pokemon;
 pub mod throw_ball;
+pub mod throw_ball_for;
+pub mod session;
+pub mod transfer_balls;
+pub mod ball_tokens;
+pub mod craft_balls;
+pub mod sell_back_balls;
 pub mod consume_randomness;
+pub mod claim_prize;
+pub mod close_fulfilled_requests;
+pub mod cancel_stale_request;
 pub mod deposit_nft;
 pub mod withdraw_nft;
 pub mod withdraw_revenue;
+pub mod distribute_revenue;
 pub mod admin;
+pub mod authority;
+pub mod config_change;
+pub mod migrate;
 
 pub use initialize::*;
 pub use purchase_balls::*;
+pub use purchase_balls_with_usdc::*;
+pub use purchase_balls_with_sol::*;
+pub use purchase_balls_at_oracle_price::*;
+pub use purchase_balls_for::*;
+pub use self_exclude::*;
 pub use spawn_pokemon::*;
 pub use force_spawn_pokemon::*;
 pub use reposition_pokemon::*;
 pub use despawn_pokemon::*;
 pub use throw_ball::*;
+pub use throw_ball_for::*;
+pub use session::*;
+pub use transfer_balls::*;
+pub use ball_tokens::*;
+pub use craft_balls::*;
+pub use sell_back_balls::*;
 pub use consume_randomness::*;
+pub use claim_prize::*;
+pub use close_fulfilled_requests::*;
+pub use cancel_stale_request::*;
 pub use deposit_nft::*;
 pub use withdraw_nft::*;
 pub use withdraw_revenue::*;
+pub use distribute_revenue::*;
 pub use admin::*;
+pub use authority::*;
+pub use config_change::*;
+pub use migrate::*;
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GameError;
+use crate::events::{BallPurchased, BundlePurchased};
+use crate::constants::*;
+use crate::instructions::purchase_balls::{charge_solballs, credit_balls, PurchaseBalls, SolBallsCharge};
+
+pub fn handler(
+    ctx: Context<PurchaseBalls>,
+    quantities: [u32; 4],
+    max_total_cost: u64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+
+    // Bundles are credited to inventory only
+    require!(
+        ctx.accounts.ball_mint.is_none() && ctx.accounts.player_ball_account.is_none(),
+        GameError::BallTokenAccountsMissing
+    );
+
+    // At least one tier must be bought
+    require!(
+        quantities.iter().any(|quantity| *quantity > 0),
+        GameError::ZeroQuantity
+    );
+
+    // Price, cap and charge the whole bundle with a single transfer
+    let accounts = &mut *ctx.accounts;
+    let tier_costs = charge_solballs(
+        SolBallsCharge {
+            buyer: &accounts.player,
+            game_config: &mut accounts.game_config,
+            price_feed: accounts.price_feed.as_ref(),
+            solballs_mint: accounts.solballs_mint.as_deref(),
+            buyer_token_account: &accounts.player_token_account,
+            game_solballs_account: &accounts.game_solballs_account,
+            spend_tracker: &mut accounts.spend_tracker,
+            spend_tracker_bump: ctx.bumps.spend_tracker,
+            token_program: &accounts.token_program,
+        },
+        &quantities,
+        max_total_cost,
+    )?;
+    let total_cost: u64 = tier_costs.iter().sum();
+
+    // Update player inventory and emit one event per tier bought
+    let buyer = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+    for (ball_type, quantity) in quantities.iter().enumerate() {
+        if *quantity == 0 {
+            continue;
+        }
+        credit_balls(
+            &mut ctx.accounts.player_inventory,
+            buyer,
+            ctx.bumps.player_inventory,
+            ball_type as u8,
+            *quantity,
+        )?;
+        ctx.accounts.player_inventory.record_refundable(
+            &mut ctx.accounts.game_config,
+            ball_type as u8,
+            *quantity,
+            tier_costs[ball_type],
+            now,
+        )?;
+
+        emit!(BallPurchased {
+            buyer,
+            ball_type: ball_type as u8,
+            quantity: *quantity,
+            total_cost: tier_costs[ball_type],
+            currency: CURRENCY_SOLBALLS,
+        });
+    }
+
+    emit!(BundlePurchased {
+        buyer,
+        quantities,
+        total_cost,
+    });
+
+    msg!(
+        "Player {} purchased bundle {:?} for {} SolBalls",
+        buyer, quantities, total_cost
+    );
+
+    Ok(())
+}
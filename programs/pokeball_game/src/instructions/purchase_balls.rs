@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::state::*;
 use crate::errors::GameError;
-use crate::events::BallPurchased;
+use crate::events::{BallPurchased, BallTokensMinted};
 use crate::constants::*;
+use crate::oracle::OraclePrice;
+use crate::instructions::ball_tokens::mint_ball_tokens;
 
 #[derive(Accounts)]
 pub struct PurchaseBalls<'info> {
@@ -45,6 +47,37 @@ pub struct PurchaseBalls<'info> {
     )]
     pub player_inventory: Account<'info, PlayerInventory>,
 
+    /// Player spend tracker PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    /// Optional ball token mint for `ball_type`. Pass together with
+    /// `player_ball_account` to receive the balls as SPL tokens.
+    #[account(mut)]
+    pub ball_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Optional player token account for `ball_mint` (must already exist).
+    #[account(
+        mut,
+        constraint = player_ball_account.owner == player.key(),
+    )]
+    pub player_ball_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// SolBalls/USD price account, required once GameConfig.usd_ball_prices is set.
+    /// CHECK: Address pinned by GameConfig.price_feed; data parsed by OraclePrice::load.
+    #[account(address = game_config.price_feed @ GameError::InvalidPriceFeed)]
+    pub price_feed: Option<AccountInfo<'info>>,
+
+    /// The SolBalls mint (for decimals), required once GameConfig.usd_ball_prices is set.
+    #[account(address = game_config.solballs_mint)]
+    pub solballs_mint: Option<Box<Account<'info, Mint>>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -53,7 +86,13 @@ pub fn handler(
     ctx: Context<PurchaseBalls>,
     ball_type: u8,
     quantity: u32,
+    max_total_cost: u64,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+
     // Validate ball type
     require!(
         (ball_type as usize) < NUM_BALL_TYPES,
@@ -63,77 +102,312 @@ pub fn handler(
     // Validate quantity
     require!(quantity > 0, GameError::ZeroQuantity);
 
-    let game_config = &ctx.accounts.game_config;
-    let price_per_ball = game_config.ball_prices[ball_type as usize];
+    // Ball tokens instead of inventory counters?
+    match (&ctx.accounts.ball_mint, &ctx.accounts.player_ball_account) {
+        (Some(ball_mint), Some(player_ball_account)) => {
+            require!(
+                !ctx.accounts.game_config.is_paused(PAUSE_BALL_TOKENS),
+                GameError::BallTokensPaused
+            );
+            let expected_mint = ctx.accounts.game_config.ball_mints[ball_type as usize];
+            require!(expected_mint != Pubkey::default(), GameError::BallMintNotSet);
+            require!(
+                ball_mint.key() == expected_mint && player_ball_account.mint == expected_mint,
+                GameError::InvalidBallMint
+            );
+        }
+        (None, None) => {}
+        _ => return err!(GameError::BallTokenAccountsMissing),
+    }
 
-    // Calculate total cost (checked multiplication)
-    let total_cost = (price_per_ball as u128)
-        .checked_mul(quantity as u128)
-        .ok_or(GameError::MathOverflow)?;
+    let mut quantities = [0u32; NUM_BALL_TYPES];
+    quantities[ball_type as usize] = quantity;
 
-    // Ensure it fits in u64
-    require!(total_cost <= u64::MAX as u128, GameError::MathOverflow);
-    let total_cost = total_cost as u64;
+    let accounts = &mut *ctx.accounts;
+    let tier_costs = charge_solballs(
+        SolBallsCharge {
+            buyer: &accounts.player,
+            game_config: &mut accounts.game_config,
+            price_feed: accounts.price_feed.as_ref(),
+            solballs_mint: accounts.solballs_mint.as_deref(),
+            buyer_token_account: &accounts.player_token_account,
+            game_solballs_account: &accounts.game_solballs_account,
+            spend_tracker: &mut accounts.spend_tracker,
+            spend_tracker_bump: ctx.bumps.spend_tracker,
+            token_program: &accounts.token_program,
+        },
+        &quantities,
+        max_total_cost,
+    )?;
+    let total_cost = tier_costs[ball_type as usize];
 
-    // Enforce maximum purchase amount per transaction (matches ApeChain MAX_PURCHASE_USD)
-    require!(
-        total_cost <= MAX_PURCHASE_AMOUNT,
-        GameError::PurchaseExceedsMax
+    if let (Some(ball_mint), Some(player_ball_account)) =
+        (&ctx.accounts.ball_mint, &ctx.accounts.player_ball_account)
+    {
+        // Deliver as ball tokens; the purchase still counts towards lifetime stats
+        record_purchase(
+            &mut ctx.accounts.player_inventory,
+            ctx.accounts.player.key(),
+            ctx.bumps.player_inventory,
+            quantity,
+        )?;
+        mint_ball_tokens(
+            ctx.accounts.token_program.to_account_info(),
+            ball_mint.to_account_info(),
+            player_ball_account.to_account_info(),
+            ctx.accounts.game_config.to_account_info(),
+            ctx.accounts.game_config.bump,
+            quantity,
+        )?;
+
+        emit!(BallTokensMinted {
+            player: ctx.accounts.player.key(),
+            ball_type,
+            quantity,
+        });
+    } else {
+        // Update player inventory
+        credit_balls(
+            &mut ctx.accounts.player_inventory,
+            ctx.accounts.player.key(),
+            ctx.bumps.player_inventory,
+            ball_type,
+            quantity,
+        )?;
+        ctx.accounts.player_inventory.record_refundable(
+            &mut ctx.accounts.game_config,
+            ball_type,
+            quantity,
+            total_cost,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    // Emit event
+    emit!(BallPurchased {
+        buyer: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+        total_cost,
+        currency: CURRENCY_SOLBALLS,
+    });
+
+    msg!(
+        "Player {} purchased {} balls of type {} for {} SolBalls",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type,
+        total_cost
     );
 
-    // Check player has sufficient balance
+    Ok(())
+}
+
+/// Accounts a SolBalls purchase is charged against.
+pub(crate) struct SolBallsCharge<'a, 'info> {
+    /// Wallet paying for the balls; its spend limits apply.
+    pub buyer: &'a Signer<'info>,
+    pub game_config: &'a mut Account<'info, GameConfig>,
+    /// Oracle price account, required once GameConfig.usd_ball_prices is set.
+    pub price_feed: Option<&'a AccountInfo<'info>>,
+    /// SolBalls mint (for decimals), required once GameConfig.usd_ball_prices is set.
+    pub solballs_mint: Option<&'a Account<'info, Mint>>,
+    pub buyer_token_account: &'a Account<'info, TokenAccount>,
+    pub game_solballs_account: &'a Account<'info, TokenAccount>,
+    pub spend_tracker: &'a mut Account<'info, SpendTracker>,
+    pub spend_tracker_bump: u8,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Charge the buyer for `quantities` balls of each tier: price them, enforce the
+/// buyer's `max_total_cost` and spend limits, transfer the SolBalls into the game
+/// account and count the revenue. Returns the cost of each tier.
+pub(crate) fn charge_solballs(
+    charge: SolBallsCharge,
+    quantities: &[u32; NUM_BALL_TYPES],
+    max_total_cost: u64,
+) -> Result<[u64; NUM_BALL_TYPES]> {
+    let tier_costs = solballs_costs(
+        charge.game_config,
+        charge.price_feed,
+        charge.solballs_mint,
+        quantities,
+    )?;
+    let total_cost = tier_costs
+        .iter()
+        .try_fold(0u64, |total, cost| total.checked_add(*cost))
+        .ok_or(GameError::MathOverflow)?;
+
+    // Buyer's slippage bound
+    require!(total_cost <= max_total_cost, GameError::SlippageExceeded);
+
+    // Enforce self-exclusion and the buyer's spend limits
+    track_spend(
+        charge.spend_tracker,
+        charge.game_config,
+        charge.buyer.key(),
+        charge.spend_tracker_bump,
+        CURRENCY_SOLBALLS,
+        total_cost,
+    )?;
+
+    // Check buyer has sufficient balance
     require!(
-        ctx.accounts.player_token_account.amount >= total_cost,
+        charge.buyer_token_account.amount >= total_cost,
         GameError::InsufficientSolBalls
     );
 
-    // Transfer SolBalls from player to game account
+    // Transfer SolBalls from buyer to game account
     let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
+        charge.token_program.to_account_info(),
         Transfer {
-            from: ctx.accounts.player_token_account.to_account_info(),
-            to: ctx.accounts.game_solballs_account.to_account_info(),
-            authority: ctx.accounts.player.to_account_info(),
+            from: charge.buyer_token_account.to_account_info(),
+            to: charge.game_solballs_account.to_account_info(),
+            authority: charge.buyer.to_account_info(),
         },
     );
     token::transfer(transfer_ctx, total_cost)?;
 
-    // Update player inventory
-    let inventory = &mut ctx.accounts.player_inventory;
-    if inventory.player == Pubkey::default() {
-        // First-time initialization
-        inventory.player = ctx.accounts.player.key();
-        inventory.bump = ctx.bumps.player_inventory;
+    // Update game revenue
+    charge.game_config.total_revenue = charge.game_config.total_revenue
+        .checked_add(total_cost)
+        .ok_or(GameError::MathOverflow)?;
+
+    Ok(tier_costs)
+}
+
+/// SolBalls cost of `quantities` balls of each tier. Once GameConfig.usd_ball_prices
+/// is set the USD prices are converted at the oracle price; otherwise
+/// GameConfig.ball_prices applies. The per-transaction cap is enforced either way.
+fn solballs_costs(
+    game_config: &GameConfig,
+    price_feed: Option<&AccountInfo>,
+    solballs_mint: Option<&Account<Mint>>,
+    quantities: &[u32; NUM_BALL_TYPES],
+) -> Result<[u64; NUM_BALL_TYPES]> {
+    let mut tier_costs = [0u64; NUM_BALL_TYPES];
+
+    if !game_config.uses_usd_pricing() {
+        let mut total_cost: u128 = 0;
+        for (ball_type, quantity) in quantities.iter().enumerate() {
+            let cost = (game_config.ball_prices[ball_type] as u128)
+                .checked_mul(*quantity as u128)
+                .ok_or(GameError::MathOverflow)?;
+            total_cost = total_cost
+                .checked_add(cost)
+                .ok_or(GameError::MathOverflow)?;
+            tier_costs[ball_type] = cost as u64;
+        }
+
+        // Enforce maximum purchase amount per transaction (matches ApeChain MAX_PURCHASE_USD)
+        require!(
+            total_cost <= MAX_PURCHASE_AMOUNT as u128,
+            GameError::PurchaseExceedsMax
+        );
+        return Ok(tier_costs);
+    }
+
+    require!(
+        game_config.price_feed != Pubkey::default(),
+        GameError::PriceFeedNotSet
+    );
+    let (Some(price_feed), Some(solballs_mint)) = (price_feed, solballs_mint) else {
+        return err!(GameError::PriceFeedMissing);
+    };
+
+    // Total in USD micro-units, capped like the ApeChain MAX_PURCHASE_USD
+    let mut tier_usd = [0u64; NUM_BALL_TYPES];
+    let mut total_usd: u128 = 0;
+    for (ball_type, quantity) in quantities.iter().enumerate() {
+        if *quantity == 0 {
+            continue;
+        }
+        let usd_per_ball = game_config.usd_ball_prices[ball_type];
+        require!(usd_per_ball > 0, GameError::UsdPriceNotSet);
+
+        let usd = (usd_per_ball as u128)
+            .checked_mul(*quantity as u128)
+            .ok_or(GameError::MathOverflow)?;
+        total_usd = total_usd
+            .checked_add(usd)
+            .ok_or(GameError::MathOverflow)?;
+        tier_usd[ball_type] = usd as u64;
+    }
+    require!(
+        total_usd <= MAX_PURCHASE_USD as u128,
+        GameError::PurchaseExceedsMax
+    );
+
+    // Convert each tier to SolBalls at the current oracle price
+    let price = OraclePrice::load(price_feed)?;
+    price.check(
+        Clock::get()?.unix_timestamp,
+        game_config.max_price_age,
+        game_config.max_price_conf_bps,
+    )?;
+    for (ball_type, usd) in tier_usd.iter().enumerate() {
+        if *usd == 0 {
+            continue;
+        }
+        let cost = price.usd_to_token_amount(*usd, solballs_mint.decimals)?;
+        require!(cost > 0, GameError::InvalidPriceFeed);
+        tier_costs[ball_type] = cost;
     }
 
+    Ok(tier_costs)
+}
+
+/// Credit purchased balls to an inventory, initializing it on first use.
+pub(crate) fn credit_balls(
+    inventory: &mut PlayerInventory,
+    player: Pubkey,
+    bump: u8,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    record_purchase(inventory, player, bump, quantity)?;
+
     inventory.balls[ball_type as usize] = inventory.balls[ball_type as usize]
         .checked_add(quantity)
         .ok_or(GameError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Count a purchase in the inventory's lifetime stats, initializing it on first use.
+pub(crate) fn record_purchase(
+    inventory: &mut PlayerInventory,
+    player: Pubkey,
+    bump: u8,
+    quantity: u32,
+) -> Result<()> {
+    if inventory.player == Pubkey::default() {
+        // First-time initialization
+        inventory.player = player;
+        inventory.bump = bump;
+    }
+
     inventory.total_purchased = inventory.total_purchased
         .checked_add(quantity as u64)
         .ok_or(GameError::MathOverflow)?;
 
-    // Update game revenue
-    let game_config = &mut ctx.accounts.game_config;
-    game_config.total_revenue = game_config.total_revenue
-        .checked_add(total_cost)
-        .ok_or(GameError::MathOverflow)?;
-
-    // Emit event
-    emit!(BallPurchased {
-        buyer: ctx.accounts.player.key(),
-        ball_type,
-        quantity,
-        total_cost,
-    });
+    Ok(())
+}
 
-    msg!(
-        "Player {} purchased {} balls of type {} for {} SolBalls",
-        ctx.accounts.player.key(),
-        quantity,
-        ball_type,
-        total_cost
-    );
+/// Record a purchase against the player's spend tracker, initializing it on first use.
+pub(crate) fn track_spend(
+    tracker: &mut SpendTracker,
+    game_config: &GameConfig,
+    player: Pubkey,
+    bump: u8,
+    currency: u8,
+    amount: u64,
+) -> Result<()> {
+    if tracker.player == Pubkey::default() {
+        // First-time initialization
+        tracker.player = player;
+        tracker.bump = bump;
+    }
 
-    Ok(())
+    tracker.record_spend(game_config, currency, amount, Clock::get()?.unix_timestamp)
 }
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::BallPurchased;
+use crate::constants::*;
+use crate::instructions::purchase_balls::{charge_solballs, credit_balls, SolBallsCharge};
+
+#[derive(Accounts)]
+pub struct PurchaseBallsAtOraclePrice<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// SolBalls/USD price account in Pyth's layout.
+    /// CHECK: Address pinned by GameConfig.price_feed; data parsed by OraclePrice::load.
+    #[account(
+        constraint = game_config.price_feed != Pubkey::default() @ GameError::PriceFeedNotSet,
+        address = game_config.price_feed @ GameError::InvalidPriceFeed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    /// The SolBalls mint (for decimals).
+    #[account(address = game_config.solballs_mint)]
+    pub solballs_mint: Account<'info, Mint>,
+
+    /// Player's SolBalls token account (source).
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == game_config.solballs_mint,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// Game's SolBalls token account (destination, PDA-owned).
+    #[account(
+        mut,
+        constraint = game_solballs_account.owner == game_config.key(),
+        constraint = game_solballs_account.mint == game_config.solballs_mint,
+    )]
+    pub game_solballs_account: Account<'info, TokenAccount>,
+
+    /// Player inventory PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerInventory::LEN,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    /// Player spend tracker PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseBallsAtOraclePrice>,
+    ball_type: u8,
+    quantity: u32,
+    max_total_cost: u64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+
+    // Validate ball type
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Validate quantity
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    let usd_per_ball = ctx.accounts.game_config.usd_ball_prices[ball_type as usize];
+    require!(usd_per_ball > 0, GameError::UsdPriceNotSet);
+
+    let mut quantities = [0u32; NUM_BALL_TYPES];
+    quantities[ball_type as usize] = quantity;
+
+    let accounts = &mut *ctx.accounts;
+    let tier_costs = charge_solballs(
+        SolBallsCharge {
+            buyer: &accounts.player,
+            game_config: &mut accounts.game_config,
+            price_feed: Some(&accounts.price_feed),
+            solballs_mint: Some(&accounts.solballs_mint),
+            buyer_token_account: &accounts.player_token_account,
+            game_solballs_account: &accounts.game_solballs_account,
+            spend_tracker: &mut accounts.spend_tracker,
+            spend_tracker_bump: ctx.bumps.spend_tracker,
+            token_program: &accounts.token_program,
+        },
+        &quantities,
+        max_total_cost,
+    )?;
+    let total_cost = tier_costs[ball_type as usize];
+
+    // Update player inventory
+    credit_balls(
+        &mut ctx.accounts.player_inventory,
+        ctx.accounts.player.key(),
+        ctx.bumps.player_inventory,
+        ball_type,
+        quantity,
+    )?;
+    ctx.accounts.player_inventory.record_refundable(
+        &mut ctx.accounts.game_config,
+        ball_type,
+        quantity,
+        total_cost,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    emit!(BallPurchased {
+        buyer: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+        total_cost,
+        currency: CURRENCY_SOLBALLS,
+    });
+
+    msg!(
+        "Player {} purchased {} balls of type {} for {} SolBalls at oracle price",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type,
+        total_cost
+    );
+
+    Ok(())
+}
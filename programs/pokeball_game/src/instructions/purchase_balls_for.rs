@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::BallsGifted;
+use crate::constants::*;
+use crate::instructions::purchase_balls::{charge_solballs, credit_balls, SolBallsCharge};
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct PurchaseBallsFor<'info> {
+    /// Sponsor paying for the balls.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// Payer's SolBalls token account (source).
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key(),
+        constraint = payer_token_account.mint == game_config.solballs_mint,
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    /// Game's SolBalls token account (destination, PDA-owned).
+    #[account(
+        mut,
+        constraint = game_solballs_account.owner == game_config.key(),
+        constraint = game_solballs_account.mint == game_config.solballs_mint,
+    )]
+    pub game_solballs_account: Account<'info, TokenAccount>,
+
+    /// Recipient inventory PDA. Created by the payer if the recipient has never played.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerInventory::LEN,
+        seeds = [PLAYER_INV_SEED, recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_inventory: Account<'info, PlayerInventory>,
+
+    /// Payer's spend tracker PDA. Gifts count against the payer's limits.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    /// CHECK: Recipient's spend tracker PDA, only read to honour their self-exclusion.
+    /// Uninitialized if the recipient has never purchased.
+    #[account(
+        seeds = [SPEND_TRACKER_SEED, recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_spend_tracker: UncheckedAccount<'info>,
+
+    /// SolBalls/USD price account, required once GameConfig.usd_ball_prices is set.
+    /// CHECK: Address pinned by GameConfig.price_feed; data parsed by OraclePrice::load.
+    #[account(address = game_config.price_feed @ GameError::InvalidPriceFeed)]
+    pub price_feed: Option<AccountInfo<'info>>,
+
+    /// The SolBalls mint (for decimals), required once GameConfig.usd_ball_prices is set.
+    #[account(address = game_config.solballs_mint)]
+    pub solballs_mint: Option<Box<Account<'info, Mint>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseBallsFor>,
+    recipient: Pubkey,
+    ball_type: u8,
+    quantity: u32,
+    max_total_cost: u64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+
+    // Gifts to yourself go through purchase_balls
+    require!(
+        recipient != Pubkey::default() && recipient != ctx.accounts.payer.key(),
+        GameError::InvalidRecipient
+    );
+
+    // Validate ball type
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Validate quantity
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    // A self-excluded recipient can't be sent balls either
+    let recipient_tracker = ctx.accounts.recipient_spend_tracker.to_account_info();
+    if recipient_tracker.owner == &crate::ID {
+        let data = recipient_tracker.try_borrow_data()?;
+        let tracker = SpendTracker::try_deserialize(&mut &data[..])?;
+        require!(
+            Clock::get()?.unix_timestamp >= tracker.excluded_until,
+            GameError::RecipientSelfExcluded
+        );
+    }
+
+    let mut quantities = [0u32; NUM_BALL_TYPES];
+    quantities[ball_type as usize] = quantity;
+
+    let accounts = &mut *ctx.accounts;
+    let tier_costs = charge_solballs(
+        SolBallsCharge {
+            buyer: &accounts.payer,
+            game_config: &mut accounts.game_config,
+            price_feed: accounts.price_feed.as_ref(),
+            solballs_mint: accounts.solballs_mint.as_deref(),
+            buyer_token_account: &accounts.payer_token_account,
+            game_solballs_account: &accounts.game_solballs_account,
+            spend_tracker: &mut accounts.spend_tracker,
+            spend_tracker_bump: ctx.bumps.spend_tracker,
+            token_program: &accounts.token_program,
+        },
+        &quantities,
+        max_total_cost,
+    )?;
+    let total_cost = tier_costs[ball_type as usize];
+
+    // Credit the recipient's inventory
+    credit_balls(
+        &mut ctx.accounts.recipient_inventory,
+        recipient,
+        ctx.bumps.recipient_inventory,
+        ball_type,
+        quantity,
+    )?;
+
+    emit!(BallsGifted {
+        payer: ctx.accounts.payer.key(),
+        recipient,
+        ball_type,
+        quantity,
+        total_cost,
+    });
+
+    msg!(
+        "{} gifted {} balls of type {} to {} for {} SolBalls",
+        ctx.accounts.payer.key(),
+        quantity,
+        ball_type,
+        recipient,
+        total_cost
+    );
+
+    Ok(())
+}
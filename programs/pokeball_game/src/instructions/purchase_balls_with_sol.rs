@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::BallPurchased;
+use crate::constants::*;
+use crate::instructions::purchase_balls::{credit_balls, track_spend};
+
+#[derive(Accounts)]
+pub struct PurchaseBallsWithSol<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// Game's SOL treasury (system-owned PDA, no data). Funded to the
+    /// rent-exempt minimum at initialize/migrate_game_config.
+    #[account(
+        mut,
+        seeds = [SOL_TREASURY_SEED],
+        bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    /// Player inventory PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerInventory::LEN,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    /// Player spend tracker PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseBallsWithSol>,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+
+    // Validate ball type
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Validate quantity
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    let game_config = &ctx.accounts.game_config;
+    let price_per_ball = game_config.sol_ball_prices[ball_type as usize];
+    require!(price_per_ball > 0, GameError::SolPriceNotSet);
+
+    // Calculate total cost (checked multiplication)
+    let total_cost = (price_per_ball as u128)
+        .checked_mul(quantity as u128)
+        .ok_or(GameError::MathOverflow)?;
+
+    // Ensure it fits in u64
+    require!(total_cost <= u64::MAX as u128, GameError::MathOverflow);
+    let total_cost = total_cost as u64;
+
+    // Enforce maximum purchase amount per transaction
+    require!(
+        total_cost <= MAX_SOL_PURCHASE_AMOUNT,
+        GameError::PurchaseExceedsMax
+    );
+
+    // Enforce self-exclusion and the player's spend limits
+    track_spend(
+        &mut ctx.accounts.spend_tracker,
+        &ctx.accounts.game_config,
+        ctx.accounts.player.key(),
+        ctx.bumps.spend_tracker,
+        CURRENCY_SOL,
+        total_cost,
+    )?;
+
+    // Check player has sufficient balance
+    require!(
+        ctx.accounts.player.lamports() >= total_cost,
+        GameError::InsufficientSol
+    );
+
+    // Transfer lamports from player to the SOL treasury
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.player.to_account_info(),
+            to: ctx.accounts.sol_treasury.to_account_info(),
+        },
+    );
+    system_program::transfer(transfer_ctx, total_cost)?;
+
+    // Update player inventory
+    credit_balls(
+        &mut ctx.accounts.player_inventory,
+        ctx.accounts.player.key(),
+        ctx.bumps.player_inventory,
+        ball_type,
+        quantity,
+    )?;
+
+    // Update game SOL revenue
+    let game_config = &mut ctx.accounts.game_config;
+    game_config.total_sol_revenue = game_config.total_sol_revenue
+        .checked_add(total_cost)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(BallPurchased {
+        buyer: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+        total_cost,
+        currency: CURRENCY_SOL,
+    });
+
+    msg!(
+        "Player {} purchased {} balls of type {} for {} lamports",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type,
+        total_cost
+    );
+
+    Ok(())
+}
+
+/// Top the SOL treasury up to the rent-exempt minimum, so a first purchase
+/// smaller than that minimum doesn't fail.
+pub(crate) fn fund_sol_treasury<'info>(
+    sol_treasury: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(sol_treasury.lamports());
+    if shortfall > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: sol_treasury.clone(),
+            },
+        );
+        system_program::transfer(transfer_ctx, shortfall)?;
+    }
+    Ok(())
+}
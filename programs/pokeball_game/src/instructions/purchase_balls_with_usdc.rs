@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::BallPurchased;
+use crate::constants::*;
+use crate::instructions::purchase_balls::{credit_balls, track_spend};
+
+#[derive(Accounts)]
+pub struct PurchaseBallsWithUsdc<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The configured USDC mint.
+    #[account(address = game_config.usdc_mint)]
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Player's USDC token account (source).
+    #[account(
+        mut,
+        constraint = player_usdc_account.owner == player.key(),
+        constraint = player_usdc_account.mint == game_config.usdc_mint,
+    )]
+    pub player_usdc_account: Account<'info, TokenAccount>,
+
+    /// Game's USDC token account (destination, PDA-owned ATA).
+    /// Created on the first USDC purchase.
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = game_config,
+    )]
+    pub game_usdc_account: Account<'info, TokenAccount>,
+
+    /// Player inventory PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerInventory::LEN,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    /// Player spend tracker PDA. Created on first purchase via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseBallsWithUsdc>,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+
+    // Validate ball type
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Validate quantity
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    let game_config = &ctx.accounts.game_config;
+    let price_per_ball = game_config.usdc_ball_prices[ball_type as usize];
+    require!(price_per_ball > 0, GameError::UsdcPriceNotSet);
+
+    // Calculate total cost (checked multiplication)
+    let total_cost = (price_per_ball as u128)
+        .checked_mul(quantity as u128)
+        .ok_or(GameError::MathOverflow)?;
+
+    // Ensure it fits in u64
+    require!(total_cost <= u64::MAX as u128, GameError::MathOverflow);
+    let total_cost = total_cost as u64;
+
+    // Enforce maximum purchase amount per transaction ($49.90)
+    require!(
+        total_cost <= MAX_USDC_PURCHASE_AMOUNT,
+        GameError::PurchaseExceedsMax
+    );
+
+    // Enforce self-exclusion and the player's spend limits
+    track_spend(
+        &mut ctx.accounts.spend_tracker,
+        &ctx.accounts.game_config,
+        ctx.accounts.player.key(),
+        ctx.bumps.spend_tracker,
+        CURRENCY_USDC,
+        total_cost,
+    )?;
+
+    // Check player has sufficient balance
+    require!(
+        ctx.accounts.player_usdc_account.amount >= total_cost,
+        GameError::InsufficientUsdc
+    );
+
+    // Transfer USDC from player to game account
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.player_usdc_account.to_account_info(),
+            to: ctx.accounts.game_usdc_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, total_cost)?;
+
+    // Update player inventory
+    credit_balls(
+        &mut ctx.accounts.player_inventory,
+        ctx.accounts.player.key(),
+        ctx.bumps.player_inventory,
+        ball_type,
+        quantity,
+    )?;
+
+    // Update game USDC revenue
+    let game_config = &mut ctx.accounts.game_config;
+    game_config.total_usdc_revenue = game_config.total_usdc_revenue
+        .checked_add(total_cost)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(BallPurchased {
+        buyer: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+        total_cost,
+        currency: CURRENCY_USDC,
+    });
+
+    msg!(
+        "Player {} purchased {} balls of type {} for {} USDC",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type,
+        total_cost
+    );
+
+    Ok(())
+}
@@ -13,7 +13,7 @@ pub struct RepositionPokemon<'info> {
         seeds = [GAME_CONFIG_SEED],
         bump = game_config.bump,
         constraint = game_config.is_initialized @ GameError::NotInitialized,
-        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+        constraint = game_config.has_role(ROLE_SPAWNER, &authority.key()) @ GameError::Unauthorized,
     )]
     pub game_config: Box<Account<'info, GameConfig>>,
 
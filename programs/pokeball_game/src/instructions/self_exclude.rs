@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::SelfExclusionSet;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SelfExclude<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Player spend tracker PDA. Created here if the player has never purchased.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SelfExclude>,
+    until: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let tracker = &mut ctx.accounts.spend_tracker;
+
+    // Exclusion can only be extended, never shortened or lifted early
+    require!(
+        until > now && until >= tracker.excluded_until,
+        GameError::InvalidSelfExclusion
+    );
+
+    if tracker.player == Pubkey::default() {
+        tracker.player = ctx.accounts.player.key();
+        tracker.bump = ctx.bumps.spend_tracker;
+    }
+    tracker.excluded_until = until;
+
+    emit!(SelfExclusionSet {
+        player: ctx.accounts.player.key(),
+        excluded_until: until,
+    });
+
+    msg!(
+        "Player {} self-excluded from purchases until {}",
+        ctx.accounts.player.key(),
+        until
+    );
+
+    Ok(())
+}
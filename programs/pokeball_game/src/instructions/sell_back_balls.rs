@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::{BallsRefunded, RefundsExpired};
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SellBackBalls<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump = player_inventory.bump,
+        constraint = player_inventory.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    /// Player's SolBalls token account (refund destination).
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == game_config.solballs_mint,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// Game's SolBalls token account (refund source, PDA-owned).
+    #[account(
+        mut,
+        constraint = game_solballs_account.owner == game_config.key(),
+        constraint = game_solballs_account.mint == game_config.solballs_mint,
+    )]
+    pub game_solballs_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireRefunds<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// Any player's inventory; permissionless once its refund window has lapsed.
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player_inventory.player.as_ref()],
+        bump = player_inventory.bump,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+}
+
+pub fn handler(
+    ctx: Context<SellBackBalls>,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    let game_config = &ctx.accounts.game_config;
+    require!(
+        !game_config.is_paused(PAUSE_PURCHASES),
+        GameError::PurchasesPaused
+    );
+    require!(game_config.refund_window > 0, GameError::RefundsDisabled);
+
+    // Validate ball type
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Validate quantity
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    let inventory = &ctx.accounts.player_inventory;
+    require!(
+        inventory.in_refund_window(Clock::get()?.unix_timestamp, game_config.refund_window),
+        GameError::RefundWindowExpired
+    );
+
+    let idx = ball_type as usize;
+    let refundable = inventory.refundable_balls[idx];
+    require!(quantity <= refundable, GameError::ExceedsRefundable);
+    require!(quantity <= inventory.balls[idx], GameError::InsufficientBalls);
+
+    // Debit inventory; `paid` is the pro-rata share of what the refundable balls cost
+    let inventory = &mut ctx.accounts.player_inventory;
+    let paid = inventory.debit_balls(&mut ctx.accounts.game_config, ball_type, quantity)?;
+    inventory.total_purchased = inventory.total_purchased
+        .checked_sub(quantity as u64)
+        .ok_or(GameError::MathOverflow)?;
+
+    let game_config = &ctx.accounts.game_config;
+    let refund_amount = ((paid as u128)
+        * (BPS_DENOMINATOR - game_config.refund_haircut_bps as u64) as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    let haircut_amount = paid
+        .checked_sub(refund_amount)
+        .ok_or(GameError::MathOverflow)?;
+
+    require!(
+        ctx.accounts.game_solballs_account.amount >= refund_amount,
+        GameError::InsufficientWithdrawalAmount
+    );
+
+    // Refund SolBalls from the game account using PDA signer
+    if refund_amount > 0 {
+        let config_seeds = &[
+            GAME_CONFIG_SEED,
+            &[ctx.accounts.game_config.bump],
+        ];
+        let signer_seeds = &[&config_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.game_solballs_account.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.game_config.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, refund_amount)?;
+    }
+
+    // Only the refunded amount leaves revenue; the haircut stays
+    let game_config = &mut ctx.accounts.game_config;
+    game_config.total_revenue = game_config.total_revenue
+        .checked_sub(refund_amount)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(BallsRefunded {
+        player: ctx.accounts.player.key(),
+        ball_type,
+        quantity,
+        refund_amount,
+        haircut_amount,
+    });
+
+    msg!(
+        "Player {} sold back {} balls of type {} for {} SolBalls ({} haircut)",
+        ctx.accounts.player.key(),
+        quantity,
+        ball_type,
+        refund_amount,
+        haircut_amount
+    );
+
+    Ok(())
+}
+
+pub fn expire_handler(ctx: Context<ExpireRefunds>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let inventory = &mut ctx.accounts.player_inventory;
+    require!(
+        !inventory.in_refund_window(Clock::get()?.unix_timestamp, game_config.refund_window),
+        GameError::RefundWindowOpen
+    );
+
+    // Whatever is left of the lapsed window can no longer be sold back
+    let released = inventory.clear_refundable(game_config);
+
+    emit!(RefundsExpired {
+        player: inventory.player,
+        released,
+        outstanding_refundable: game_config.outstanding_refundable,
+    });
+
+    msg!(
+        "Expired {} refundable SolBalls for player {} ({} still outstanding)",
+        released,
+        inventory.player,
+        game_config.outstanding_refundable
+    );
+
+    Ok(())
+}
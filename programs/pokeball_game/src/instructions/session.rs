@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+use orao_solana_vrf::program::OraoVrf;
+use orao_solana_vrf::CONFIG_ACCOUNT_SEED;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::*;
+use crate::constants::*;
+use crate::instructions::throw_ball::{request_throw, ThrowRequest};
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = SessionKey::LEN,
+        seeds = [SESSION_SEED, player.key().as_ref(), session_key.as_ref()],
+        bump,
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    /// Session owner — receives the session account's rent.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, player.key().as_ref(), session.session_key.as_ref()],
+        bump = session.bump,
+        constraint = session.owner == player.key() @ GameError::Unauthorized,
+        close = player,
+    )]
+    pub session: Account<'info, SessionKey>,
+}
+
+#[derive(Accounts)]
+pub struct ThrowBallWithSession<'info> {
+    /// Ephemeral session signer. Pays the VrfRequest rent and the ORAO fee.
+    #[account(mut)]
+    pub session_signer: Signer<'info>,
+
+    /// Session owner.
+    /// CHECK: Validated against session.owner.
+    #[account(address = session.owner)]
+    pub player: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, session.owner.as_ref(), session_signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [POKEMON_SLOTS_SEED],
+        bump = pokemon_slots.bump,
+    )]
+    pub pokemon_slots: Box<Account<'info, PokemonSlots>>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump = player_inventory.bump,
+        constraint = player_inventory.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    /// VRF request PDA for tracking this throw.
+    #[account(
+        init,
+        payer = session_signer,
+        space = VrfRequest::LEN,
+        seeds = [VRF_REQ_SEED, game_config.vrf_counter.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vrf_request: Account<'info, VrfRequest>,
+
+    /// ORAO VRF network state.
+    /// CHECK: Validated by the ORAO VRF program CPI.
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_vrf.key(),
+    )]
+    pub vrf_config: AccountInfo<'info>,
+
+    /// ORAO VRF randomness account — will be created by the CPI.
+    /// CHECK: Created and validated by the ORAO VRF program.
+    #[account(mut)]
+    pub vrf_randomness: AccountInfo<'info>,
+
+    /// ORAO VRF treasury.
+    /// CHECK: Validated by the ORAO VRF program CPI.
+    #[account(mut)]
+    pub vrf_treasury: AccountInfo<'info>,
+
+    pub orao_vrf: Program<'info, OraoVrf>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_handler(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    expires_at: i64,
+    ball_allowance: [u32; 4],
+    allowed_mask: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at > now && expires_at <= now.saturating_add(MAX_SESSION_DURATION),
+        GameError::InvalidSessionExpiry
+    );
+    require!(
+        allowed_mask != 0 && allowed_mask & !SESSION_ALLOW_ALL == 0,
+        GameError::InvalidSessionPermissions
+    );
+    require!(
+        session_key != ctx.accounts.player.key() && session_key != Pubkey::default(),
+        GameError::InvalidSessionKey
+    );
+
+    let session = &mut ctx.accounts.session;
+    session.owner = ctx.accounts.player.key();
+    session.session_key = session_key;
+    session.expires_at = expires_at;
+    session.ball_allowance = ball_allowance;
+    session.allowed_mask = allowed_mask;
+    session.bump = ctx.bumps.session;
+
+    emit!(SessionCreated {
+        owner: session.owner,
+        session_key,
+        expires_at,
+        ball_allowance,
+        allowed_mask,
+    });
+
+    msg!(
+        "Session {} created for {} until {}",
+        session_key, session.owner, expires_at
+    );
+
+    Ok(())
+}
+
+pub fn revoke_handler(ctx: Context<RevokeSession>) -> Result<()> {
+    let session_key = ctx.accounts.session.session_key;
+
+    emit!(SessionRevoked {
+        owner: ctx.accounts.player.key(),
+        session_key,
+    });
+
+    msg!("Session {} revoked by {}", session_key, ctx.accounts.player.key());
+
+    Ok(())
+}
+
+pub fn throw_handler(
+    ctx: Context<ThrowBallWithSession>,
+    slot_index: u8,
+    ball_type: u8,
+) -> Result<()> {
+    let session = &mut ctx.accounts.session;
+    require!(
+        Clock::get()?.unix_timestamp < session.expires_at,
+        GameError::SessionExpired
+    );
+    require!(
+        session.allowed_mask & SESSION_ALLOW_THROW != 0,
+        GameError::SessionNotAllowed
+    );
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Debit the session allowance
+    let allowance = &mut session.ball_allowance[ball_type as usize];
+    *allowance = allowance
+        .checked_sub(1)
+        .ok_or(GameError::SessionAllowanceExceeded)?;
+
+    let accounts = ctx.accounts;
+    request_throw(
+        ThrowRequest {
+            player: accounts.player.key(),
+            payer: accounts.session_signer.to_account_info(),
+            session: accounts.session.key(),
+            game_config: &mut accounts.game_config,
+            pokemon_slots: &accounts.pokemon_slots,
+            player_inventory: &mut accounts.player_inventory,
+            vrf_request: &mut accounts.vrf_request,
+            vrf_request_bump: ctx.bumps.vrf_request,
+            vrf_config: accounts.vrf_config.to_account_info(),
+            vrf_randomness: accounts.vrf_randomness.to_account_info(),
+            vrf_treasury: accounts.vrf_treasury.to_account_info(),
+            orao_vrf: accounts.orao_vrf.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+        },
+        slot_index,
+        ball_type,
+    )
+}
+
+/// Give back the allowance a voided or cancelled session throw used. A session
+/// revoked since the throw has been closed, so there is nothing to restore.
+pub(crate) fn restore_session_allowance(
+    session: Option<AccountInfo>,
+    vrf_request: &VrfRequest,
+) -> Result<()> {
+    if vrf_request.session == Pubkey::default() {
+        return Ok(());
+    }
+    let session_info = session.ok_or(GameError::SessionAccountMissing)?;
+    if session_info.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*session_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let mut data = session_info.try_borrow_mut_data()?;
+    let mut session = SessionKey::try_deserialize(&mut &data[..])?;
+    let allowance = &mut session.ball_allowance[vrf_request.ball_type as usize];
+    *allowance = allowance
+        .checked_add(1)
+        .ok_or(GameError::MathOverflow)?;
+    session.try_serialize(&mut &mut data[..])
+}
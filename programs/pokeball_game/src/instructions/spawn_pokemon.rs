@@ -27,7 +27,7 @@ pub struct SpawnPokemon<'info> {
         seeds = [GAME_CONFIG_SEED],
         bump = game_config.bump,
         constraint = game_config.is_initialized @ GameError::NotInitialized,
-        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+        constraint = game_config.has_role(ROLE_SPAWNER, &authority.key()) @ GameError::Unauthorized,
     )]
     pub game_config: Box<Account<'info, GameConfig>>,
 
@@ -76,6 +76,11 @@ pub fn handler(
     ctx: Context<SpawnPokemon>,
     slot_index: u8,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_SPAWNS),
+        GameError::SpawnsPaused
+    );
+
     let slot_idx = slot_index as usize;
 
     // Validate slot index
@@ -83,16 +88,20 @@ pub fn handler(
 
     let pokemon_slots = &ctx.accounts.pokemon_slots;
 
-    // Check slot is empty
+    // Check slot is empty and not reserved by a pending VRF spawn
     require!(
         !pokemon_slots.slots[slot_idx].is_active,
         GameError::SlotAlreadyOccupied
     );
+    require!(
+        !pokemon_slots.is_spawn_pending(slot_idx),
+        GameError::SlotSpawnPending
+    );
 
-    // Check max active count
+    // Check max active count (pending spawns count towards the cap)
     let game_config = &ctx.accounts.game_config;
     require!(
-        pokemon_slots.active_count < game_config.max_active_pokemon,
+        pokemon_slots.occupied_count() < game_config.max_active_pokemon as u32,
         GameError::MaxActivePokemonReached
     );
 
@@ -111,14 +120,21 @@ pub fn handler(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     orao_solana_vrf::cpi::request_v2(cpi_ctx, seed)?;
 
+    // Reserve the slot until the VRF result is consumed or the request is cancelled
+    ctx.accounts.pokemon_slots.set_spawn_pending(slot_idx, true);
+
     // Store VRF request state
     let vrf_request = &mut ctx.accounts.vrf_request;
     vrf_request.request_type = VRF_TYPE_SPAWN;
     vrf_request.player = ctx.accounts.authority.key();
+    vrf_request.payer = ctx.accounts.authority.key();
     vrf_request.slot_index = slot_index;
+    vrf_request.pokemon_id = 0;
     vrf_request.ball_type = 0;
     vrf_request.seed = seed;
     vrf_request.is_fulfilled = false;
+    vrf_request.requested_at = Clock::get()?.unix_timestamp;
+    vrf_request.session = Pubkey::default();
     vrf_request.bump = ctx.bumps.vrf_request;
 
     // Increment VRF counter
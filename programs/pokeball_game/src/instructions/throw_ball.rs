@@ -70,11 +70,64 @@ pub struct ThrowBall<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts shared by every throw entry point (direct, relayed, session).
+pub(crate) struct ThrowRequest<'a, 'info> {
+    /// Owner of the inventory the ball comes from.
+    pub player: Pubkey,
+    /// Pays the ORAO fee (and has already paid the VrfRequest rent).
+    pub payer: AccountInfo<'info>,
+    /// SessionKey PDA whose allowance paid for the ball (default = none).
+    pub session: Pubkey,
+    pub game_config: &'a mut Account<'info, GameConfig>,
+    pub pokemon_slots: &'a Account<'info, PokemonSlots>,
+    pub player_inventory: &'a mut Account<'info, PlayerInventory>,
+    pub vrf_request: &'a mut Account<'info, VrfRequest>,
+    pub vrf_request_bump: u8,
+    pub vrf_config: AccountInfo<'info>,
+    pub vrf_randomness: AccountInfo<'info>,
+    pub vrf_treasury: AccountInfo<'info>,
+    pub orao_vrf: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
 pub fn handler(
     ctx: Context<ThrowBall>,
     slot_index: u8,
     ball_type: u8,
 ) -> Result<()> {
+    let accounts = ctx.accounts;
+    request_throw(
+        ThrowRequest {
+            player: accounts.player.key(),
+            payer: accounts.player.to_account_info(),
+            session: Pubkey::default(),
+            game_config: &mut accounts.game_config,
+            pokemon_slots: &accounts.pokemon_slots,
+            player_inventory: &mut accounts.player_inventory,
+            vrf_request: &mut accounts.vrf_request,
+            vrf_request_bump: ctx.bumps.vrf_request,
+            vrf_config: accounts.vrf_config.to_account_info(),
+            vrf_randomness: accounts.vrf_randomness.to_account_info(),
+            vrf_treasury: accounts.vrf_treasury.to_account_info(),
+            orao_vrf: accounts.orao_vrf.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+        },
+        slot_index,
+        ball_type,
+    )
+}
+
+/// Validate the throw, take the ball from the inventory and request ORAO VRF.
+pub(crate) fn request_throw(
+    req: ThrowRequest,
+    slot_index: u8,
+    ball_type: u8,
+) -> Result<()> {
+    require!(
+        !req.game_config.is_paused(PAUSE_THROWS),
+        GameError::ThrowsPaused
+    );
+
     let slot_idx = slot_index as usize;
 
     // Validate slot index
@@ -87,8 +140,7 @@ pub fn handler(
     );
 
     // Check slot is active
-    let pokemon_slots = &ctx.accounts.pokemon_slots;
-    let slot = &pokemon_slots.slots[slot_idx];
+    let slot = &req.pokemon_slots.slots[slot_idx];
     require!(slot.is_active, GameError::SlotNotActive);
 
     // Check throw attempts haven't maxed out
@@ -98,17 +150,14 @@ pub fn handler(
     );
 
     // Check player has balls of this type
-    let inventory = &ctx.accounts.player_inventory;
+    let inventory = req.player_inventory;
     require!(
         inventory.balls[ball_type as usize] > 0,
         GameError::InsufficientBalls
     );
 
     // Decrement ball count
-    let inventory = &mut ctx.accounts.player_inventory;
-    inventory.balls[ball_type as usize] = inventory.balls[ball_type as usize]
-        .checked_sub(1)
-        .ok_or(GameError::MathOverflow)?;
+    inventory.debit_balls(req.game_config, ball_type, 1)?;
     inventory.total_throws = inventory.total_throws
         .checked_add(1)
         .ok_or(GameError::MathOverflow)?;
@@ -117,42 +166,44 @@ pub fn handler(
     // ApeChain behavior: attempts are tracked at resolution time in consume_randomness.
     // This ensures unresolved VRF requests don't consume attempts.
 
-    let pokemon_id = ctx.accounts.pokemon_slots.slots[slot_idx].pokemon_id;
+    let pokemon_id = slot.pokemon_id;
 
     // Generate VRF seed using shared helper
-    let game_config = &ctx.accounts.game_config;
+    let game_config = req.game_config;
     let seed = make_vrf_seed(game_config.vrf_counter, VRF_TYPE_THROW);
 
     // CPI to ORAO VRF to request randomness (v2 API)
-    let cpi_program = ctx.accounts.orao_vrf.to_account_info();
     let cpi_accounts = orao_solana_vrf::cpi::accounts::RequestV2 {
-        payer: ctx.accounts.player.to_account_info(),
-        network_state: ctx.accounts.vrf_config.to_account_info(),
-        treasury: ctx.accounts.vrf_treasury.to_account_info(),
-        request: ctx.accounts.vrf_randomness.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
+        payer: req.payer.clone(),
+        network_state: req.vrf_config,
+        treasury: req.vrf_treasury,
+        request: req.vrf_randomness,
+        system_program: req.system_program,
     };
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new(req.orao_vrf, cpi_accounts);
     orao_solana_vrf::cpi::request_v2(cpi_ctx, seed)?;
 
     // Store VRF request state
-    let vrf_request = &mut ctx.accounts.vrf_request;
+    let vrf_request = req.vrf_request;
     vrf_request.request_type = VRF_TYPE_THROW;
-    vrf_request.player = ctx.accounts.player.key();
+    vrf_request.player = req.player;
+    vrf_request.payer = req.payer.key();
     vrf_request.slot_index = slot_index;
+    vrf_request.pokemon_id = pokemon_id;
     vrf_request.ball_type = ball_type;
     vrf_request.seed = seed;
     vrf_request.is_fulfilled = false;
-    vrf_request.bump = ctx.bumps.vrf_request;
+    vrf_request.requested_at = Clock::get()?.unix_timestamp;
+    vrf_request.session = req.session;
+    vrf_request.bump = req.vrf_request_bump;
 
     // Increment VRF counter
-    let game_config = &mut ctx.accounts.game_config;
     game_config.vrf_counter = game_config.vrf_counter
         .checked_add(1)
         .ok_or(GameError::MathOverflow)?;
 
     emit!(ThrowAttempted {
-        thrower: ctx.accounts.player.key(),
+        thrower: req.player,
         pokemon_id,
         ball_type,
         slot_index,
@@ -161,7 +212,7 @@ pub fn handler(
 
     msg!(
         "Player {} threw ball type {} at Pokemon {} (slot {})",
-        ctx.accounts.player.key(),
+        req.player,
         ball_type,
         pokemon_id,
         slot_index
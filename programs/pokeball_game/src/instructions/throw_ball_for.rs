@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+#[allow(deprecated)]
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar_program, load_current_index_checked, load_instruction_at_checked,
+};
+use orao_solana_vrf::program::OraoVrf;
+use orao_solana_vrf::CONFIG_ACCOUNT_SEED;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::ThrowRelayed;
+use crate::constants::*;
+use crate::instructions::throw_ball::{request_throw, ThrowRequest};
+
+#[derive(Accounts)]
+pub struct ThrowBallFor<'info> {
+    /// Configured relayer. Pays the VrfRequest rent and the ORAO fee.
+    #[account(
+        mut,
+        constraint = relayer.key() == game_config.relayer @ GameError::InvalidRelayer,
+    )]
+    pub relayer: Signer<'info>,
+
+    /// Player the throw is made for.
+    /// CHECK: Authorized by the ed25519 signature checked in the handler.
+    pub player: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [POKEMON_SLOTS_SEED],
+        bump = pokemon_slots.bump,
+    )]
+    pub pokemon_slots: Box<Account<'info, PokemonSlots>>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, player.key().as_ref()],
+        bump = player_inventory.bump,
+        constraint = player_inventory.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_inventory: Account<'info, PlayerInventory>,
+
+    /// VRF request PDA for tracking this throw.
+    #[account(
+        init,
+        payer = relayer,
+        space = VrfRequest::LEN,
+        seeds = [VRF_REQ_SEED, game_config.vrf_counter.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vrf_request: Account<'info, VrfRequest>,
+
+    /// ORAO VRF network state.
+    /// CHECK: Validated by the ORAO VRF program CPI.
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_vrf.key(),
+    )]
+    pub vrf_config: AccountInfo<'info>,
+
+    /// ORAO VRF randomness account — will be created by the CPI.
+    /// CHECK: Created and validated by the ORAO VRF program.
+    #[account(mut)]
+    pub vrf_randomness: AccountInfo<'info>,
+
+    /// ORAO VRF treasury.
+    /// CHECK: Validated by the ORAO VRF program CPI.
+    #[account(mut)]
+    pub vrf_treasury: AccountInfo<'info>,
+
+    /// Instructions sysvar — used to read the preceding ed25519 instruction.
+    /// CHECK: Address is checked.
+    #[account(address = instructions_sysvar_program::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub orao_vrf: Program<'info, OraoVrf>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Message the player signs off-chain:
+/// program_id (32) || slot_index (1) || ball_type (1) || nonce (8, LE) || expiry (8, LE).
+pub fn relay_message(slot_index: u8, ball_type: u8, nonce: u64, expiry: i64) -> [u8; 50] {
+    let mut message = [0u8; 50];
+    message[..32].copy_from_slice(crate::ID.as_ref());
+    message[32] = slot_index;
+    message[33] = ball_type;
+    message[34..42].copy_from_slice(&nonce.to_le_bytes());
+    message[42..50].copy_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+/// Check that the instruction right before this one is an ed25519 precompile
+/// verifying `signer`'s signature over `message`.
+fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, GameError::MissingEd25519Instruction);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ix.program_id == ED25519_PROGRAM_ID && ix.accounts.is_empty(),
+        GameError::MissingEd25519Instruction
+    );
+
+    // Layout: num_signatures (1) || padding (1) || offsets (14) || pubkey || signature || message
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, GameError::InvalidRelaySignature);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+
+    // All offsets must point into this same instruction's data
+    require!(
+        signature_ix_index == u16::MAX
+            && public_key_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        GameError::InvalidRelaySignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(GameError::InvalidRelaySignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(GameError::InvalidRelaySignature)?;
+
+    require!(public_key == signer.as_ref(), GameError::InvalidRelaySignature);
+    require!(signed_message == message, GameError::InvalidRelaySignature);
+
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<ThrowBallFor>,
+    slot_index: u8,
+    ball_type: u8,
+    nonce: u64,
+    expiry: i64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.game_config.relayer != Pubkey::default(),
+        GameError::InvalidRelayer
+    );
+    require!(
+        Clock::get()?.unix_timestamp <= expiry,
+        GameError::RelayIntentExpired
+    );
+    require!(
+        nonce == ctx.accounts.player_inventory.relay_nonce,
+        GameError::InvalidRelayNonce
+    );
+
+    let player = ctx.accounts.player.key();
+    verify_ed25519_instruction(
+        &ctx.accounts.instructions_sysvar,
+        &player,
+        &relay_message(slot_index, ball_type, nonce, expiry),
+    )?;
+
+    // Advance the nonce so this signed intent can't be replayed
+    ctx.accounts.player_inventory.relay_nonce = nonce
+        .checked_add(1)
+        .ok_or(GameError::MathOverflow)?;
+
+    let accounts = ctx.accounts;
+    request_throw(
+        ThrowRequest {
+            player,
+            payer: accounts.relayer.to_account_info(),
+            session: Pubkey::default(),
+            game_config: &mut accounts.game_config,
+            pokemon_slots: &accounts.pokemon_slots,
+            player_inventory: &mut accounts.player_inventory,
+            vrf_request: &mut accounts.vrf_request,
+            vrf_request_bump: ctx.bumps.vrf_request,
+            vrf_config: accounts.vrf_config.to_account_info(),
+            vrf_randomness: accounts.vrf_randomness.to_account_info(),
+            vrf_treasury: accounts.vrf_treasury.to_account_info(),
+            orao_vrf: accounts.orao_vrf.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+        },
+        slot_index,
+        ball_type,
+    )?;
+
+    emit!(ThrowRelayed {
+        player,
+        relayer: accounts.relayer.key(),
+        nonce,
+    });
+
+    msg!("Throw relayed for {} by {} (nonce {})", player, accounts.relayer.key(), nonce);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::GameError;
+use crate::events::{BallsReceived, BallsSent};
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct TransferBalls<'info> {
+    /// Sender. Pays for the recipient's inventory if it doesn't exist yet.
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_INV_SEED, sender.key().as_ref()],
+        bump = sender_inventory.bump,
+        constraint = sender_inventory.player == sender.key() @ GameError::Unauthorized,
+    )]
+    pub sender_inventory: Account<'info, PlayerInventory>,
+
+    /// Recipient inventory PDA. Created via init_if_needed.
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = PlayerInventory::LEN,
+        seeds = [PLAYER_INV_SEED, recipient.as_ref()],
+        bump,
+    )]
+    pub recipient_inventory: Account<'info, PlayerInventory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<TransferBalls>,
+    recipient: Pubkey,
+    ball_type: u8,
+    quantity: u32,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.game_config.is_paused(PAUSE_TRANSFERS),
+        GameError::TransfersPaused
+    );
+
+    let sender = ctx.accounts.sender.key();
+    require!(
+        recipient != Pubkey::default() && recipient != sender,
+        GameError::InvalidRecipient
+    );
+
+    // Validate ball type
+    require!(
+        (ball_type as usize) < NUM_BALL_TYPES,
+        GameError::InvalidBallType
+    );
+
+    // Validate quantity
+    require!(quantity > 0, GameError::ZeroQuantity);
+
+    // Debit sender
+    let sender_inventory = &mut ctx.accounts.sender_inventory;
+    sender_inventory.debit_balls(&mut ctx.accounts.game_config, ball_type, quantity)?;
+    let remaining = sender_inventory.balls[ball_type as usize];
+
+    // Credit recipient (not counted as a purchase)
+    let recipient_inventory = &mut ctx.accounts.recipient_inventory;
+    if recipient_inventory.player == Pubkey::default() {
+        // First-time initialization
+        recipient_inventory.player = recipient;
+        recipient_inventory.bump = ctx.bumps.recipient_inventory;
+    }
+    let new_balance = recipient_inventory.balls[ball_type as usize]
+        .checked_add(quantity)
+        .ok_or(GameError::MathOverflow)?;
+    recipient_inventory.balls[ball_type as usize] = new_balance;
+
+    emit!(BallsSent {
+        sender,
+        recipient,
+        ball_type,
+        quantity,
+        remaining,
+    });
+
+    emit!(BallsReceived {
+        recipient,
+        sender,
+        ball_type,
+        quantity,
+        new_balance,
+    });
+
+    msg!(
+        "{} transferred {} balls of type {} to {}",
+        sender,
+        quantity,
+        ball_type,
+        recipient
+    );
+
+    Ok(())
+}
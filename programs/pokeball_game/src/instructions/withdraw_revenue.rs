@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::state::*;
 use crate::errors::GameError;
-use crate::events::RevenueWithdrawn;
+use crate::events::{RevenueWithdrawn, SplitBucketWithdrawn};
 use crate::constants::*;
+use crate::instructions::distribute_revenue::{split_revenue, RevenueSplit};
 
 #[derive(Accounts)]
 pub struct WithdrawRevenue<'info> {
+    /// Treasurer. Pays rent for the bucket accounts on first use.
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -16,7 +19,7 @@ pub struct WithdrawRevenue<'info> {
         seeds = [GAME_CONFIG_SEED],
         bump = game_config.bump,
         constraint = game_config.is_initialized @ GameError::NotInitialized,
-        constraint = game_config.authority == authority.key() @ GameError::Unauthorized,
+        constraint = game_config.has_role(ROLE_TREASURER, &authority.key()) @ GameError::Unauthorized,
     )]
     pub game_config: Box<Account<'info, GameConfig>>,
 
@@ -27,6 +30,9 @@ pub struct WithdrawRevenue<'info> {
     )]
     pub treasury_config: Account<'info, TreasuryConfig>,
 
+    #[account(address = game_config.solballs_mint)]
+    pub solballs_mint: Account<'info, Mint>,
+
     /// Game's SolBalls token account (source, PDA-owned).
     #[account(
         mut,
@@ -35,13 +41,189 @@ pub struct WithdrawRevenue<'info> {
     )]
     pub game_solballs_account: Account<'info, TokenAccount>,
 
-    /// Authority's SolBalls token account (destination).
+    /// Treasury wallet's SolBalls token account (destination of the treasury share).
+    #[account(
+        mut,
+        constraint = treasury_solballs_account.owner == treasury_config.treasury_wallet @ GameError::InvalidTreasuryAccount,
+        constraint = treasury_solballs_account.mint == game_config.solballs_mint @ GameError::InvalidTreasuryAccount,
+    )]
+    pub treasury_solballs_account: Account<'info, TokenAccount>,
+
+    /// Prize pool SolBalls account (PDA, owned by game_config).
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [PRIZE_POOL_SEED],
+        bump,
+        token::mint = solballs_mint,
+        token::authority = game_config,
+    )]
+    pub prize_pool: Account<'info, TokenAccount>,
+
+    /// Reserve SolBalls account (PDA, owned by game_config).
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [RESERVE_SEED],
+        bump,
+        token::mint = solballs_mint,
+        token::authority = game_config,
+    )]
+    pub reserve: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUsdcRevenue<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_TREASURER, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+
+    /// Game's USDC token account (source, PDA-owned).
+    #[account(
+        mut,
+        constraint = game_usdc_account.owner == game_config.key(),
+        constraint = game_usdc_account.mint == game_config.usdc_mint,
+    )]
+    pub game_usdc_account: Account<'info, TokenAccount>,
+
+    /// Treasury wallet's USDC token account (destination).
+    #[account(
+        mut,
+        constraint = treasury_usdc_account.owner == treasury_config.treasury_wallet @ GameError::InvalidTreasuryAccount,
+        constraint = treasury_usdc_account.mint == game_config.usdc_mint,
+    )]
+    pub treasury_usdc_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolRevenue<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_TREASURER, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+
+    /// Game's SOL treasury (source, system-owned PDA).
+    #[account(
+        mut,
+        seeds = [SOL_TREASURY_SEED],
+        bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    /// Treasury wallet (destination).
+    #[account(
+        mut,
+        address = treasury_config.treasury_wallet @ GameError::InvalidTreasuryAccount,
+    )]
+    pub treasury_wallet: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPrizePool<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_TREASURER, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+
+    /// Prize pool SolBalls account (source, PDA owned by game_config).
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED],
+        bump,
+    )]
+    pub prize_pool: Account<'info, TokenAccount>,
+
+    /// Treasury wallet's SolBalls token account (destination).
+    #[account(
+        mut,
+        constraint = treasury_solballs_account.owner == treasury_config.treasury_wallet @ GameError::InvalidTreasuryAccount,
+        constraint = treasury_solballs_account.mint == game_config.solballs_mint @ GameError::InvalidTreasuryAccount,
+    )]
+    pub treasury_solballs_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReserve<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_CONFIG_SEED],
+        bump = game_config.bump,
+        constraint = game_config.is_initialized @ GameError::NotInitialized,
+        constraint = game_config.has_role(ROLE_TREASURER, &authority.key()) @ GameError::Unauthorized,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury_config.bump,
+    )]
+    pub treasury_config: Account<'info, TreasuryConfig>,
+
+    /// Reserve SolBalls account (source, PDA owned by game_config).
     #[account(
         mut,
-        constraint = authority_solballs_account.owner == authority.key(),
-        constraint = authority_solballs_account.mint == game_config.solballs_mint,
+        seeds = [RESERVE_SEED],
+        bump,
     )]
-    pub authority_solballs_account: Account<'info, TokenAccount>,
+    pub reserve: Account<'info, TokenAccount>,
+
+    /// Treasury wallet's SolBalls token account (destination).
+    #[account(
+        mut,
+        constraint = treasury_solballs_account.owner == treasury_config.treasury_wallet @ GameError::InvalidTreasuryAccount,
+        constraint = treasury_solballs_account.mint == game_config.solballs_mint @ GameError::InvalidTreasuryAccount,
+    )]
+    pub treasury_solballs_account: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
 }
@@ -49,13 +231,58 @@ pub struct WithdrawRevenue<'info> {
 pub fn handler(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
     require!(amount > 0, GameError::InsufficientWithdrawalAmount);
 
-    // Check the game has enough SolBalls
+    // Check the game has enough SolBalls, leaving what is still refundable
+    require!(
+        ctx.accounts.game_config.distributable(ctx.accounts.game_solballs_account.amount) >= amount,
+        GameError::InsufficientWithdrawalAmount
+    );
+
+    // SolBalls only leave the game account through the revenue split
+    let accounts = ctx.accounts;
+    let shares = split_revenue(
+        RevenueSplit {
+            game_config: &accounts.game_config,
+            treasury_config: &mut accounts.treasury_config,
+            game_solballs_account: accounts.game_solballs_account.to_account_info(),
+            treasury_solballs_account: accounts.treasury_solballs_account.to_account_info(),
+            prize_pool: accounts.prize_pool.to_account_info(),
+            reserve: accounts.reserve.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        },
+        amount,
+    )?;
+    let to_treasury = shares[SPLIT_TREASURY];
+
+    // Update treasury tracking
+    let treasury_config = &mut accounts.treasury_config;
+    treasury_config.total_withdrawn = treasury_config.total_withdrawn
+        .checked_add(to_treasury)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(RevenueWithdrawn {
+        recipient: treasury_config.treasury_wallet,
+        amount: to_treasury,
+        currency: CURRENCY_SOLBALLS,
+    });
+
+    msg!(
+        "Withdrawn {} SolBalls to treasury {} ({} split)",
+        to_treasury, treasury_config.treasury_wallet, amount
+    );
+
+    Ok(())
+}
+
+pub fn usdc_handler(ctx: Context<WithdrawUsdcRevenue>, amount: u64) -> Result<()> {
+    require!(amount > 0, GameError::InsufficientWithdrawalAmount);
+
+    // Check the game has enough USDC
     require!(
-        ctx.accounts.game_solballs_account.amount >= amount,
+        ctx.accounts.game_usdc_account.amount >= amount,
         GameError::InsufficientWithdrawalAmount
     );
 
-    // Transfer SolBalls from game PDA to authority using PDA signer
+    // Transfer USDC from game PDA to the treasury using PDA signer
     let config_seeds = &[
         GAME_CONFIG_SEED,
         &[ctx.accounts.game_config.bump],
@@ -65,8 +292,8 @@ pub fn handler(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
-            from: ctx.accounts.game_solballs_account.to_account_info(),
-            to: ctx.accounts.authority_solballs_account.to_account_info(),
+            from: ctx.accounts.game_usdc_account.to_account_info(),
+            to: ctx.accounts.treasury_usdc_account.to_account_info(),
             authority: ctx.accounts.game_config.to_account_info(),
         },
         signer_seeds,
@@ -75,19 +302,154 @@ pub fn handler(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
 
     // Update treasury tracking
     let treasury_config = &mut ctx.accounts.treasury_config;
-    treasury_config.total_withdrawn = treasury_config.total_withdrawn
+    treasury_config.total_usdc_withdrawn = treasury_config.total_usdc_withdrawn
+        .checked_add(amount)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(RevenueWithdrawn {
+        recipient: treasury_config.treasury_wallet,
+        amount,
+        currency: CURRENCY_USDC,
+    });
+
+    msg!(
+        "Withdrawn {} USDC to treasury {}",
+        amount, treasury_config.treasury_wallet
+    );
+
+    Ok(())
+}
+
+pub fn sol_handler(ctx: Context<WithdrawSolRevenue>, amount: u64) -> Result<()> {
+    require!(amount > 0, GameError::InsufficientWithdrawalAmount);
+
+    // The treasury PDA must stay rent-exempt
+    let rent_reserve = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.sol_treasury.lamports().saturating_sub(rent_reserve);
+    require!(amount <= available, GameError::InsufficientWithdrawalAmount);
+
+    // Transfer lamports from the SOL treasury PDA to the treasury wallet using PDA signer
+    let treasury_seeds = &[
+        SOL_TREASURY_SEED,
+        &[ctx.bumps.sol_treasury],
+    ];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.sol_treasury.to_account_info(),
+            to: ctx.accounts.treasury_wallet.to_account_info(),
+        },
+        signer_seeds,
+    );
+    system_program::transfer(transfer_ctx, amount)?;
+
+    // Update treasury tracking
+    let treasury_config = &mut ctx.accounts.treasury_config;
+    treasury_config.total_sol_withdrawn = treasury_config.total_sol_withdrawn
         .checked_add(amount)
         .ok_or(GameError::MathOverflow)?;
 
     emit!(RevenueWithdrawn {
-        recipient: ctx.accounts.authority.key(),
+        recipient: treasury_config.treasury_wallet,
+        amount,
+        currency: CURRENCY_SOL,
+    });
+
+    msg!(
+        "Withdrawn {} lamports to treasury {}",
+        amount, treasury_config.treasury_wallet
+    );
+
+    Ok(())
+}
+
+pub fn prize_pool_handler(ctx: Context<WithdrawPrizePool>, amount: u64) -> Result<()> {
+    transfer_from_bucket(
+        &ctx.accounts.game_config,
+        &ctx.accounts.prize_pool,
+        ctx.accounts.treasury_solballs_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+
+    let treasury_config = &mut ctx.accounts.treasury_config;
+    treasury_config.total_from_prize_pool = treasury_config.total_from_prize_pool
+        .checked_add(amount)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(SplitBucketWithdrawn {
+        bucket: SPLIT_PRIZE_POOL as u8,
+        recipient: treasury_config.treasury_wallet,
+        amount,
+        total_withdrawn: treasury_config.total_from_prize_pool,
+    });
+
+    msg!(
+        "Withdrawn {} SolBalls from the prize pool to treasury {}",
+        amount, treasury_config.treasury_wallet
+    );
+
+    Ok(())
+}
+
+pub fn reserve_handler(ctx: Context<WithdrawReserve>, amount: u64) -> Result<()> {
+    transfer_from_bucket(
+        &ctx.accounts.game_config,
+        &ctx.accounts.reserve,
+        ctx.accounts.treasury_solballs_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+
+    let treasury_config = &mut ctx.accounts.treasury_config;
+    treasury_config.total_from_reserve = treasury_config.total_from_reserve
+        .checked_add(amount)
+        .ok_or(GameError::MathOverflow)?;
+
+    emit!(SplitBucketWithdrawn {
+        bucket: SPLIT_RESERVE as u8,
+        recipient: treasury_config.treasury_wallet,
         amount,
+        total_withdrawn: treasury_config.total_from_reserve,
     });
 
     msg!(
-        "Withdrawn {} SolBalls to authority {}",
-        amount, ctx.accounts.authority.key()
+        "Withdrawn {} SolBalls from the reserve to treasury {}",
+        amount, treasury_config.treasury_wallet
     );
 
     Ok(())
 }
+
+/// Move `amount` SolBalls out of a game_config-owned split bucket.
+fn transfer_from_bucket<'info>(
+    game_config: &Account<'info, GameConfig>,
+    bucket: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount > 0 && bucket.amount >= amount,
+        GameError::InsufficientWithdrawalAmount
+    );
+
+    let config_seeds = &[
+        GAME_CONFIG_SEED,
+        &[game_config.bump],
+    ];
+    let signer_seeds = &[&config_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        Transfer {
+            from: bucket.to_account_info(),
+            to: destination,
+            authority: game_config.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
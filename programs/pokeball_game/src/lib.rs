@@ -5,8 +5,10 @@ pub mod instructions;
 pub mod errors;
 pub mod events;
 pub mod constants;
+pub mod oracle;
 
 use instructions::*;
+use state::ConfigValues;
 
 declare_id!("B93VJQKD5UW8qfNsLrQ4ZQvTG6AG7PZsR6o2WeBiboBZ");
 
@@ -29,15 +31,138 @@ pub mod pokeball_game {
 
     /// Player purchases balls by transferring SolBalls tokens.
     /// Auto-creates PlayerInventory PDA on first purchase.
+    /// Optionally delivers the balls as SPL ball tokens (pass ball_mint + player_ball_account).
+    /// Priced at GameConfig.ball_prices, or once usd_ball_prices is set at the oracle
+    /// price (pass price_feed + solballs_mint). Fails if the cost exceeds max_total_cost.
     pub fn purchase_balls(
         ctx: Context<PurchaseBalls>,
         ball_type: u8,
         quantity: u32,
+        max_total_cost: u64,
     ) -> Result<()> {
-        instructions::purchase_balls::handler(ctx, ball_type, quantity)
+        instructions::purchase_balls::handler(ctx, ball_type, quantity, max_total_cost)
     }
 
-    /// Authority requests a random spawn via ORAO VRF.
+    /// Player purchases several ball tiers at once with a single SolBalls transfer.
+    /// The combined cost is checked against the purchase cap and max_total_cost.
+    pub fn purchase_ball_bundle(
+        ctx: Context<PurchaseBalls>,
+        quantities: [u32; 4],
+        max_total_cost: u64,
+    ) -> Result<()> {
+        instructions::purchase_ball_bundle::handler(ctx, quantities, max_total_cost)
+    }
+
+    /// Sponsor pays SolBalls to credit another wallet's inventory.
+    /// Creates the recipient's PlayerInventory if needed. Priced like purchase_balls.
+    pub fn purchase_balls_for(
+        ctx: Context<PurchaseBallsFor>,
+        recipient: Pubkey,
+        ball_type: u8,
+        quantity: u32,
+        max_total_cost: u64,
+    ) -> Result<()> {
+        instructions::purchase_balls_for::handler(ctx, recipient, ball_type, quantity, max_total_cost)
+    }
+
+    /// Player purchases balls by transferring USDC at GameConfig.usdc_ball_prices.
+    /// Auto-creates PlayerInventory PDA and the game's USDC account on first use.
+    pub fn purchase_balls_with_usdc(
+        ctx: Context<PurchaseBallsWithUsdc>,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::purchase_balls_with_usdc::handler(ctx, ball_type, quantity)
+    }
+
+    /// Player purchases balls by transferring lamports to the SOL treasury PDA
+    /// at GameConfig.sol_ball_prices.
+    pub fn purchase_balls_with_sol(
+        ctx: Context<PurchaseBallsWithSol>,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::purchase_balls_with_sol::handler(ctx, ball_type, quantity)
+    }
+
+    /// Player purchases balls priced in USD (GameConfig.usd_ball_prices), paid in
+    /// SolBalls converted at the oracle price. Fails if the cost exceeds max_total_cost.
+    pub fn purchase_balls_at_oracle_price(
+        ctx: Context<PurchaseBallsAtOraclePrice>,
+        ball_type: u8,
+        quantity: u32,
+        max_total_cost: u64,
+    ) -> Result<()> {
+        instructions::purchase_balls_at_oracle_price::handler(ctx, ball_type, quantity, max_total_cost)
+    }
+
+    /// Player sends balls from their inventory to another wallet's.
+    /// Creates the recipient's PlayerInventory if needed. Blocked by PAUSE_TRANSFERS.
+    pub fn transfer_balls(
+        ctx: Context<TransferBalls>,
+        recipient: Pubkey,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::transfer_balls::handler(ctx, recipient, ball_type, quantity)
+    }
+
+    /// Player moves balls out of their inventory as SPL ball tokens.
+    /// Creates the player's ball token ATA if needed. Blocked by PAUSE_BALL_TOKENS.
+    pub fn withdraw_ball_tokens(
+        ctx: Context<WithdrawBallTokens>,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::ball_tokens::withdraw_handler(ctx, ball_type, quantity)
+    }
+
+    /// Player burns SPL ball tokens and receives the balls in their inventory.
+    /// Creates the PlayerInventory PDA if needed. Blocked by PAUSE_BALL_TOKENS.
+    pub fn redeem_ball_tokens(
+        ctx: Context<RedeemBallTokens>,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::ball_tokens::redeem_handler(ctx, ball_type, quantity)
+    }
+
+    /// Player upgrades balls: burns `craft_ratios[from_ball_type] * count` balls of one tier
+    /// for `count` balls of the next tier. Blocked by PAUSE_CRAFTING.
+    pub fn craft_balls(
+        ctx: Context<CraftBalls>,
+        from_ball_type: u8,
+        count: u32,
+    ) -> Result<()> {
+        instructions::craft_balls::handler(ctx, from_ball_type, count)
+    }
+
+    /// Player sells back balls bought with SolBalls within the refund window,
+    /// receiving the price paid minus the refund haircut. Blocked by PAUSE_PURCHASES.
+    pub fn sell_back_balls(
+        ctx: Context<SellBackBalls>,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::sell_back_balls::handler(ctx, ball_type, quantity)
+    }
+
+    /// Anyone clears a player's lapsed refund window, releasing its SolBalls
+    /// from GameConfig.outstanding_refundable so they can be distributed.
+    pub fn expire_refunds(ctx: Context<ExpireRefunds>) -> Result<()> {
+        instructions::sell_back_balls::expire_handler(ctx)
+    }
+
+    /// Player blocks their own purchases until `until` (Unix timestamp).
+    /// An exclusion can be extended but never shortened.
+    pub fn self_exclude(
+        ctx: Context<SelfExclude>,
+        until: i64,
+    ) -> Result<()> {
+        instructions::self_exclude::handler(ctx, until)
+    }
+
+    /// Spawner requests a random spawn via ORAO VRF.
     pub fn spawn_pokemon(
         ctx: Context<SpawnPokemon>,
         slot_index: u8,
@@ -45,17 +170,18 @@ pub mod pokeball_game {
         instructions::spawn_pokemon::handler(ctx, slot_index)
     }
 
-    /// Authority spawns a Pokemon at specific coordinates (no VRF).
+    /// Spawner spawns a Pokemon of the given rarity at specific coordinates (no VRF).
     pub fn force_spawn_pokemon(
         ctx: Context<ForceSpawnPokemon>,
         slot_index: u8,
         pos_x: u16,
         pos_y: u16,
+        rarity: u8,
     ) -> Result<()> {
-        instructions::force_spawn_pokemon::handler(ctx, slot_index, pos_x, pos_y)
+        instructions::force_spawn_pokemon::handler(ctx, slot_index, pos_x, pos_y, rarity)
     }
 
-    /// Authority repositions an existing Pokemon.
+    /// Spawner repositions an existing Pokemon.
     pub fn reposition_pokemon(
         ctx: Context<RepositionPokemon>,
         slot_index: u8,
@@ -65,7 +191,7 @@ pub mod pokeball_game {
         instructions::reposition_pokemon::handler(ctx, slot_index, new_pos_x, new_pos_y)
     }
 
-    /// Authority despawns a Pokemon from a slot.
+    /// Spawner despawns a Pokemon from a slot.
     pub fn despawn_pokemon(
         ctx: Context<DespawnPokemon>,
         slot_index: u8,
@@ -82,22 +208,86 @@ pub mod pokeball_game {
         instructions::throw_ball::handler(ctx, slot_index, ball_type)
     }
 
+    /// Configured relayer submits a throw signed off-chain by the player (ed25519).
+    /// The preceding instruction must be the ed25519 precompile over the intent.
+    pub fn throw_ball_for(
+        ctx: Context<ThrowBallFor>,
+        slot_index: u8,
+        ball_type: u8,
+        nonce: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        instructions::throw_ball_for::handler(ctx, slot_index, ball_type, nonce, expiry)
+    }
+
+    /// Player authorizes an ephemeral session key to throw on their behalf,
+    /// with an expiry, per-ball-type allowance and permission mask.
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expires_at: i64,
+        ball_allowance: [u32; 4],
+        allowed_mask: u8,
+    ) -> Result<()> {
+        instructions::session::create_handler(ctx, session_key, expires_at, ball_allowance, allowed_mask)
+    }
+
+    /// Player revokes a session key and reclaims its rent.
+    pub fn revoke_session(
+        ctx: Context<RevokeSession>,
+    ) -> Result<()> {
+        instructions::session::revoke_handler(ctx)
+    }
+
+    /// Session key throws a ball from its owner's inventory, debiting the session allowance.
+    pub fn throw_ball_with_session(
+        ctx: Context<ThrowBallWithSession>,
+        slot_index: u8,
+        ball_type: u8,
+    ) -> Result<()> {
+        instructions::session::throw_handler(ctx, slot_index, ball_type)
+    }
+
     /// Anyone can call after ORAO fulfills randomness.
     /// Determines catch/miss for throws, sets position for spawns.
+    /// Closes the VrfRequest and refunds its rent to the original payer.
     pub fn consume_randomness<'info>(
         ctx: Context<'_, '_, 'info, 'info, ConsumeRandomness<'info>>,
     ) -> Result<()> {
         instructions::consume_randomness::handler(ctx)
     }
 
-    /// Authority deposits a Metaplex NFT into the vault.
+    /// Winner or any cranker delivers a caught NFT from the vault to the winner's ATA.
+    pub fn claim_prize(
+        ctx: Context<ClaimPrize>,
+    ) -> Result<()> {
+        instructions::claim_prize::handler(ctx)
+    }
+
+    /// Anyone can cancel a VRF request ORAO never fulfilled once it has timed out.
+    /// Refunds the ball for throws and closes the request.
+    pub fn cancel_stale_request(
+        ctx: Context<CancelStaleRequest>,
+    ) -> Result<()> {
+        instructions::cancel_stale_request::handler(ctx)
+    }
+
+    /// Anyone can close already-fulfilled VrfRequest accounts, refunding rent to their payers.
+    /// remaining_accounts: pairs of [vrf_request, payer].
+    pub fn close_fulfilled_requests<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseFulfilledRequests<'info>>,
+    ) -> Result<()> {
+        instructions::close_fulfilled_requests::handler(ctx)
+    }
+
+    /// Vault manager deposits a Metaplex NFT into the vault.
     pub fn deposit_nft(
         ctx: Context<DepositNft>,
     ) -> Result<()> {
         instructions::deposit_nft::handler(ctx)
     }
 
-    /// Authority withdraws an NFT from the vault (admin recovery).
+    /// Vault manager withdraws an NFT from the vault (admin recovery).
     pub fn withdraw_nft(
         ctx: Context<WithdrawNft>,
         nft_index: u8,
@@ -105,37 +295,217 @@ pub mod pokeball_game {
         instructions::withdraw_nft::handler(ctx, nft_index)
     }
 
-    /// Authority updates ball price for a tier.
-    pub fn set_ball_price(
+    /// Treasurer moves `amount` SolBalls out of the game account through the
+    /// revenue split; only the treasury share goes to the treasury wallet.
+    pub fn withdraw_revenue(
+        ctx: Context<WithdrawRevenue>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_revenue::handler(ctx, amount)
+    }
+
+    /// Treasurer withdraws USDC revenue from game account to the treasury wallet.
+    pub fn withdraw_usdc_revenue(
+        ctx: Context<WithdrawUsdcRevenue>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_revenue::usdc_handler(ctx, amount)
+    }
+
+    /// Treasurer withdraws lamports from the SOL treasury PDA to the treasury wallet.
+    pub fn withdraw_sol_revenue(
+        ctx: Context<WithdrawSolRevenue>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_revenue::sol_handler(ctx, amount)
+    }
+
+    /// Anyone moves the game SolBalls balance into the treasury wallet,
+    /// prize pool PDA and reserve PDA per TreasuryConfig.split_bps.
+    pub fn distribute_revenue(
+        ctx: Context<DistributeRevenue>,
+    ) -> Result<()> {
+        instructions::distribute_revenue::handler(ctx)
+    }
+
+    /// Authority sets the revenue split table (basis points, must sum to 10,000).
+    pub fn set_revenue_split(
+        ctx: Context<SetRevenueSplit>,
+        split_bps: [u16; 3],
+    ) -> Result<()> {
+        instructions::distribute_revenue::set_split_handler(ctx, split_bps)
+    }
+
+    /// Authority sets the circuit-breaker pause bitmask (PAUSE_* flags).
+    pub fn set_pause_flags(
+        ctx: Context<AuthorityConfig>,
+        pause_flags: u8,
+    ) -> Result<()> {
+        instructions::admin::set_pause_flags_handler(ctx, pause_flags)
+    }
+
+    /// Authority proposes a new authority. Takes effect once accepted.
+    pub fn propose_authority(
+        ctx: Context<AuthorityConfig>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::authority::propose_authority_handler(ctx, new_authority)
+    }
+
+    /// Pending authority accepts the transfer. Moves GameConfig and NftVault authority.
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        instructions::authority::accept_authority_handler(ctx)
+    }
+
+    /// Authority cancels a pending authority transfer.
+    pub fn cancel_authority_transfer(
+        ctx: Context<AuthorityConfig>,
+    ) -> Result<()> {
+        instructions::authority::cancel_authority_transfer_handler(ctx)
+    }
+
+    /// Authority assigns a role (ROLE_*) to a key, replacing any previous holder.
+    pub fn grant_role(
+        ctx: Context<AuthorityConfig>,
+        role: u8,
+        holder: Pubkey,
+    ) -> Result<()> {
+        instructions::authority::grant_role_handler(ctx, role, holder)
+    }
+
+    /// Authority clears a role.
+    pub fn revoke_role(
+        ctx: Context<AuthorityConfig>,
+        role: u8,
+    ) -> Result<()> {
+        instructions::authority::revoke_role_handler(ctx, role)
+    }
+
+    /// Config admin queues new prices, catch rates, max-active values, the price feed
+    /// and the refund policy.
+    /// They can be executed once the configured delay has elapsed.
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        values: ConfigValues,
+    ) -> Result<()> {
+        instructions::config_change::queue_handler(ctx, values)
+    }
+
+    /// Anyone can apply a queued config change after its eta.
+    pub fn execute_config_change(
+        ctx: Context<ExecuteConfigChange>,
+    ) -> Result<()> {
+        instructions::config_change::execute_handler(ctx)
+    }
+
+    /// Config admin cancels the queued config change.
+    pub fn cancel_config_change(
+        ctx: Context<CancelConfigChange>,
+    ) -> Result<()> {
+        instructions::config_change::cancel_handler(ctx)
+    }
+
+    /// Authority updates the delay applied to queued config changes (1 hour to 30 days).
+    /// Increases apply immediately; reductions only once the old delay has elapsed.
+    pub fn set_config_change_delay(
+        ctx: Context<AuthorityConfig>,
+        new_delay: i64,
+    ) -> Result<()> {
+        instructions::config_change::set_delay_handler(ctx, new_delay)
+    }
+
+    /// Config admin updates how long a VRF request may stay unfulfilled before it can be cancelled.
+    pub fn set_vrf_timeout(
         ctx: Context<AdminConfig>,
-        ball_type: u8,
-        new_price: u64,
+        new_timeout: i64,
+    ) -> Result<()> {
+        instructions::admin::set_vrf_timeout_handler(ctx, new_timeout)
+    }
+
+    /// Config admin sets the relayer allowed to submit throw_ball_for (default pubkey disables it).
+    pub fn set_relayer(
+        ctx: Context<AdminConfig>,
+        new_relayer: Pubkey,
     ) -> Result<()> {
-        instructions::admin::set_ball_price_handler(ctx, ball_type, new_price)
+        instructions::admin::set_relayer_handler(ctx, new_relayer)
     }
 
-    /// Authority updates catch rate for a tier.
-    pub fn set_catch_rate(
+    /// Authority changes the treasury wallet (TreasuryConfig.treasury_wallet).
+    pub fn set_treasury(
+        ctx: Context<SetTreasury>,
+        new_treasury: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::set_treasury_handler(ctx, new_treasury)
+    }
+
+    /// ConfigAdmin sets per-wallet daily and weekly spend limits, indexed by currency
+    /// (SolBalls, USDC, SOL). 0 = no limit.
+    pub fn set_spend_limits(
         ctx: Context<AdminConfig>,
+        daily_spend_limits: [u64; 3],
+        weekly_spend_limits: [u64; 3],
+    ) -> Result<()> {
+        instructions::admin::set_spend_limits_handler(ctx, daily_spend_limits, weekly_spend_limits)
+    }
+
+    /// ConfigAdmin creates the SPL mint for a ball tier (PDA, mint authority = GameConfig).
+    pub fn create_ball_mint(
+        ctx: Context<CreateBallMint>,
         ball_type: u8,
-        new_rate: u8,
     ) -> Result<()> {
-        instructions::admin::set_catch_rate_handler(ctx, ball_type, new_rate)
+        instructions::ball_tokens::create_mint_handler(ctx, ball_type)
     }
 
-    /// Authority withdraws SolBalls revenue from game account.
-    pub fn withdraw_revenue(
-        ctx: Context<WithdrawRevenue>,
+    /// ConfigAdmin sets the crafting ratios [poke->great, great->ultra, ultra->master].
+    /// Each is 0 (recipe disabled) or 2-1000.
+    pub fn set_craft_ratios(
+        ctx: Context<AdminConfig>,
+        new_ratios: [u32; 3],
+    ) -> Result<()> {
+        instructions::admin::set_craft_ratios_handler(ctx, new_ratios)
+    }
+
+    /// Authority grows a legacy PokemonSlots account to the layout with per-slot rarity.
+    /// Existing Pokemon become common. One-time; fails once migrated.
+    pub fn migrate_pokemon_slots(ctx: Context<MigratePokemonSlots>) -> Result<()> {
+        instructions::migrate::pokemon_slots_handler(ctx)
+    }
+
+    /// Authority grows the launch-layout GameConfig and TreasuryConfig to the
+    /// current layout. New fields get their initialize defaults. One-time.
+    pub fn migrate_game_config(ctx: Context<MigrateGameConfig>) -> Result<()> {
+        instructions::migrate::game_config_handler(ctx)
+    }
+
+    /// Anyone grows a launch-layout PlayerInventory to the current layout, paying the extra rent.
+    pub fn migrate_player_inventory(
+        ctx: Context<MigratePlayerInventory>,
+        player: Pubkey,
+    ) -> Result<()> {
+        instructions::migrate::player_inventory_handler(ctx, player)
+    }
+
+    /// Anyone grows a pending launch-layout VrfRequest so it can be consumed or
+    /// cancelled. A migrated throw resolves as voided (ball refunded).
+    pub fn migrate_vrf_request(ctx: Context<MigrateVrfRequest>) -> Result<()> {
+        instructions::migrate::vrf_request_handler(ctx)
+    }
+
+    /// Treasurer withdraws SolBalls from the prize pool PDA to the treasury wallet.
+    pub fn withdraw_prize_pool(
+        ctx: Context<WithdrawPrizePool>,
         amount: u64,
     ) -> Result<()> {
-        instructions::withdraw_revenue::handler(ctx, amount)
+        instructions::withdraw_revenue::prize_pool_handler(ctx, amount)
     }
 
-    /// Authority updates max active Pokemon soft cap.
-    pub fn set_max_active_pokemon(
-        ctx: Context<AdminConfig>,
-        new_max: u8,
+    /// Treasurer withdraws SolBalls from the reserve PDA to the treasury wallet.
+    pub fn withdraw_reserve(
+        ctx: Context<WithdrawReserve>,
+        amount: u64,
     ) -> Result<()> {
-        instructions::admin::set_max_active_pokemon_handler(ctx, new_max)
+        instructions::withdraw_revenue::reserve_handler(ctx, amount)
     }
 }
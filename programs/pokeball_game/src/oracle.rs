@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GameError;
+use crate::constants::PYTH_PROGRAM_IDS;
+
+/// Pyth price account magic number.
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+/// Pyth price account layout version.
+const PYTH_VERSION: u32 = 2;
+/// Pyth account type: price.
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+/// Pyth aggregate price status: trading.
+const PYTH_STATUS_TRADING: u32 = 1;
+
+// Byte offsets into a Pyth v2 price account.
+const OFFSET_MAGIC: usize = 0;
+const OFFSET_VERSION: usize = 4;
+const OFFSET_ACCOUNT_TYPE: usize = 8;
+const OFFSET_EXPO: usize = 20;
+const OFFSET_TIMESTAMP: usize = 96;
+const OFFSET_AGG_PRICE: usize = 208;
+const OFFSET_AGG_CONF: usize = 216;
+const OFFSET_AGG_STATUS: usize = 224;
+const PYTH_PRICE_MIN_LEN: usize = 240;
+
+/// Aggregate price read from a Pyth price account: `price * 10^expo`.
+#[derive(Clone, Copy, Debug)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Parse a price account in Pyth's v2 layout. Only the fields the game
+    /// needs are read; the account address is pinned by GameConfig.price_feed
+    /// and it must be owned by a Pyth oracle program.
+    pub fn load(feed: &AccountInfo) -> Result<Self> {
+        require!(
+            PYTH_PROGRAM_IDS.contains(feed.owner),
+            GameError::InvalidPriceFeed
+        );
+
+        let data = feed.try_borrow_data()?;
+        require!(data.len() >= PYTH_PRICE_MIN_LEN, GameError::InvalidPriceFeed);
+
+        let read_u32 = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+        let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+        require!(
+            read_u32(OFFSET_MAGIC) == PYTH_MAGIC
+                && read_u32(OFFSET_VERSION) == PYTH_VERSION
+                && read_u32(OFFSET_ACCOUNT_TYPE) == PYTH_ACCOUNT_TYPE_PRICE,
+            GameError::InvalidPriceFeed
+        );
+        require!(
+            read_u32(OFFSET_AGG_STATUS) == PYTH_STATUS_TRADING,
+            GameError::InvalidPriceFeed
+        );
+
+        let price = OraclePrice {
+            price: read_u64(OFFSET_AGG_PRICE) as i64,
+            conf: read_u64(OFFSET_AGG_CONF),
+            expo: read_u32(OFFSET_EXPO) as i32,
+            publish_time: read_u64(OFFSET_TIMESTAMP) as i64,
+        };
+        require!(price.price > 0, GameError::InvalidPriceFeed);
+
+        Ok(price)
+    }
+
+    /// Reject prices older than `max_age` seconds or with a confidence interval
+    /// wider than `max_conf_bps` of the price.
+    pub fn check(&self, now: i64, max_age: i64, max_conf_bps: u16) -> Result<()> {
+        require!(
+            now.saturating_sub(self.publish_time) <= max_age,
+            GameError::StalePrice
+        );
+        require!(
+            (self.conf as u128) * 10_000 <= (self.price as u128) * (max_conf_bps as u128),
+            GameError::PriceConfidenceTooWide
+        );
+        Ok(())
+    }
+
+    /// Convert a USD amount in micro-units (6 decimals) into atomic units of a
+    /// token with `decimals` decimals, priced in USD by this feed. Rounds up.
+    pub fn usd_to_token_amount(&self, usd_micro: u64, decimals: u8) -> Result<u64> {
+        // amount = usd_micro * 10^decimals * 10^-expo / (price * 10^6)
+        let mut numerator = (usd_micro as u128)
+            .checked_mul(10u128.checked_pow(decimals as u32).ok_or(GameError::MathOverflow)?)
+            .ok_or(GameError::MathOverflow)?;
+        let mut denominator = (self.price as u128)
+            .checked_mul(1_000_000)
+            .ok_or(GameError::MathOverflow)?;
+
+        let scale = 10u128
+            .checked_pow(self.expo.unsigned_abs())
+            .ok_or(GameError::MathOverflow)?;
+        if self.expo < 0 {
+            numerator = numerator.checked_mul(scale).ok_or(GameError::MathOverflow)?;
+        } else {
+            denominator = denominator.checked_mul(scale).ok_or(GameError::MathOverflow)?;
+        }
+
+        let amount = numerator
+            .checked_add(denominator - 1)
+            .ok_or(GameError::MathOverflow)?
+            / denominator;
+        require!(amount <= u64::MAX as u128, GameError::MathOverflow);
+        Ok(amount as u64)
+    }
+}
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::errors::GameError;
 
 /// Global game configuration. Single PDA for the entire game.
+/// The launch layout is `LegacyGameConfig` in migrate.rs; a deployed account must
+/// be rewritten by migrate_game_config before any other instruction can load it.
 #[account]
 pub struct GameConfig {
-    /// Owner/admin wallet that can manage spawns, prices, and withdraw revenue.
+    /// Owner/admin wallet. Assigns roles and implicitly holds all of them; revenue
+    /// withdrawals are gated on ROLE_TREASURER rather than on this key.
     pub authority: Pubkey,
-    /// Treasury wallet receiving 3% of revenue (off-chain split).
-    pub treasury: Pubkey,
     /// SolBalls SPL token mint address.
     pub solballs_mint: Pubkey,
     /// USDC SPL token mint address.
@@ -26,6 +28,61 @@ pub struct GameConfig {
     pub is_initialized: bool,
     /// VRF request counter for generating unique seeds.
     pub vrf_counter: u64,
+    /// Circuit breaker bitmask (PAUSE_* flags). 0 = nothing paused.
+    pub pause_flags: u8,
+    /// Proposed new authority awaiting acceptance (Pubkey::default() if none).
+    pub pending_authority: Pubkey,
+    /// Role holders indexed by ROLE_* (Pubkey::default() if unassigned).
+    /// The authority implicitly holds every role.
+    pub roles: [Pubkey; NUM_ROLES],
+    /// Seconds a queued config change must wait before it can be executed.
+    pub config_change_delay: i64,
+    /// Seconds after which an unfulfilled VRF request can be cancelled.
+    pub vrf_timeout: i64,
+    /// Wallet allowed to submit throw_ball_for (Pubkey::default() = relaying disabled).
+    pub relayer: Pubkey,
+    /// Ball prices in USDC atomic units: [poke, great, ultra, master]. 0 = not sold for USDC.
+    pub usdc_ball_prices: [u64; 4],
+    /// Total USDC received from all purchases.
+    pub total_usdc_revenue: u64,
+    /// Ball prices in lamports: [poke, great, ultra, master]. 0 = not sold for SOL.
+    pub sol_ball_prices: [u64; 4],
+    /// Total lamports received from all purchases.
+    pub total_sol_revenue: u64,
+    /// Ball prices in USD micro-units, paid in SolBalls at the oracle price. 0 = not sold.
+    /// Once any is set, every SolBalls purchase is priced from these instead of `ball_prices`.
+    pub usd_ball_prices: [u64; 4],
+    /// Pyth-layout SolBalls/USD price account (Pubkey::default() = oracle pricing disabled).
+    pub price_feed: Pubkey,
+    /// Maximum age in seconds of an oracle price accepted for purchases.
+    pub max_price_age: i64,
+    /// Maximum oracle confidence interval, in basis points of the price.
+    pub max_price_conf_bps: u16,
+    /// Per-wallet spend limit per day, indexed by CURRENCY_*. 0 = no limit.
+    pub daily_spend_limits: [u64; NUM_SPEND_CURRENCIES],
+    /// Per-wallet spend limit per week, indexed by CURRENCY_*. 0 = no limit.
+    pub weekly_spend_limits: [u64; NUM_SPEND_CURRENCIES],
+    /// Ball token mints (PDA, mint authority = this account), indexed by ball type.
+    /// Pubkey::default() = tier not tokenized.
+    pub ball_mints: [Pubkey; NUM_BALL_TYPES],
+    /// Balls of tier i consumed per ball of tier i + 1 crafted. 0 = recipe disabled.
+    pub craft_ratios: [u32; NUM_CRAFT_RECIPES],
+    /// Seconds after a player's last SolBalls purchase during which sell_back_balls is open.
+    /// 0 = refunds disabled.
+    pub refund_window: i64,
+    /// Share of the price paid kept on sell-back, in basis points.
+    pub refund_haircut_bps: u16,
+    /// VRF spawn odds per rarity (RARITY_*), in basis points summing to 10,000.
+    pub rarity_weights: [u16; NUM_RARITIES],
+    /// Catch-rate multiplier per rarity, in basis points (10,000 = 1x).
+    pub rarity_catch_multipliers: [u16; NUM_RARITIES],
+    /// Delay still enforced after a reduction, until `config_change_delay_floor_until`.
+    pub config_change_delay_floor: i64,
+    /// Unix timestamp at which a reduced config_change_delay takes effect.
+    pub config_change_delay_floor_until: i64,
+    /// SolBalls in the game account that players can still get back through
+    /// sell_back_balls. Held back from distribute_revenue and withdraw_revenue.
+    pub outstanding_refundable: u64,
     /// PDA bump seed.
     pub bump: u8,
 }
@@ -34,17 +91,99 @@ impl GameConfig {
     /// Account space: 8 (discriminator) + fields
     pub const LEN: usize = 8  // discriminator
         + 32  // authority
-        + 32  // treasury
         + 32  // solballs_mint
         + 32  // usdc_mint
         + (8 * 4)  // ball_prices
-        + (1 * 4)  // catch_rates
+        + NUM_BALL_TYPES  // catch_rates
         + 1   // max_active_pokemon
         + 8   // pokemon_id_counter
         + 8   // total_revenue
         + 1   // is_initialized
         + 8   // vrf_counter
+        + 1   // pause_flags
+        + 32  // pending_authority
+        + (32 * NUM_ROLES)  // roles
+        + 8   // config_change_delay
+        + 8   // vrf_timeout
+        + 32  // relayer
+        + (8 * 4)  // usdc_ball_prices
+        + 8   // total_usdc_revenue
+        + (8 * 4)  // sol_ball_prices
+        + 8   // total_sol_revenue
+        + (8 * 4)  // usd_ball_prices
+        + 32  // price_feed
+        + 8   // max_price_age
+        + 2   // max_price_conf_bps
+        + (8 * NUM_SPEND_CURRENCIES)  // daily_spend_limits
+        + (8 * NUM_SPEND_CURRENCIES)  // weekly_spend_limits
+        + (32 * NUM_BALL_TYPES)  // ball_mints
+        + (4 * NUM_CRAFT_RECIPES)  // craft_ratios
+        + 8   // refund_window
+        + 2   // refund_haircut_bps
+        + (2 * NUM_RARITIES)  // rarity_weights
+        + (2 * NUM_RARITIES)  // rarity_catch_multipliers
+        + 8   // config_change_delay_floor
+        + 8   // config_change_delay_floor_until
+        + 8   // outstanding_refundable
         + 1;  // bump
+
+    /// Delay applied to a config change queued at `now`. A reduced delay only
+    /// takes effect once the delay it replaced has fully elapsed.
+    pub fn config_change_delay_at(&self, now: i64) -> i64 {
+        if now < self.config_change_delay_floor_until {
+            self.config_change_delay.max(self.config_change_delay_floor)
+        } else {
+            self.config_change_delay
+        }
+    }
+
+    /// Whether `key` holds `role` (either as the role holder or as the authority).
+    pub fn has_role(&self, role: u8, key: &Pubkey) -> bool {
+        if *key == self.authority {
+            return true;
+        }
+        (role as usize) < NUM_ROLES
+            && self.roles[role as usize] != Pubkey::default()
+            && self.roles[role as usize] == *key
+    }
+
+    /// Whether any of the given PAUSE_* flags is currently set.
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.pause_flags & flags != 0
+    }
+
+    /// Whether SolBalls purchases are priced from `usd_ball_prices` at the oracle
+    /// price instead of from `ball_prices`.
+    pub fn uses_usd_pricing(&self) -> bool {
+        self.usd_ball_prices.iter().any(|price| *price > 0)
+    }
+
+    /// Part of a game SolBalls `balance` that can leave as revenue; the rest is
+    /// held for sell-back refunds.
+    pub fn distributable(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.outstanding_refundable)
+    }
+
+    /// Rarity for a uniform `roll` in [0, 10,000), bucketed by rarity_weights.
+    pub fn rarity_for_roll(&self, roll: u64) -> u8 {
+        let mut cumulative = 0u64;
+        for (rarity, weight) in self.rarity_weights.iter().enumerate() {
+            cumulative += *weight as u64;
+            if roll < cumulative {
+                return rarity as u8;
+            }
+        }
+        RARITY_COMMON
+    }
+
+    /// Catch rate (0-100) for `ball_type` against a Pokemon of `rarity`.
+    pub fn effective_catch_rate(&self, ball_type: usize, rarity: u8) -> u8 {
+        let multiplier = self.rarity_catch_multipliers
+            .get(rarity as usize)
+            .map_or(BPS_DENOMINATOR, |m| *m as u64);
+        let rate = self.catch_rates[ball_type] as u64 * multiplier / BPS_DENOMINATOR;
+        rate.min(100) as u8
+    }
 }
 
 /// Holds all 20 Pokemon spawn slots.
@@ -54,6 +193,8 @@ pub struct PokemonSlots {
     pub slots: [PokemonSlot; MAX_POKEMON_SLOTS],
     /// Number of currently active Pokemon.
     pub active_count: u8,
+    /// Bit i set = slot i has a VRF spawn in flight.
+    pub pending_spawn_mask: u32,
     /// PDA bump seed.
     pub bump: u8,
 }
@@ -62,7 +203,26 @@ impl PokemonSlots {
     pub const LEN: usize = 8  // discriminator
         + (PokemonSlot::LEN * MAX_POKEMON_SLOTS)  // slots
         + 1   // active_count
+        + 4   // pending_spawn_mask
         + 1;  // bump
+
+    /// Whether `slot_idx` is reserved by an unresolved VRF spawn.
+    pub fn is_spawn_pending(&self, slot_idx: usize) -> bool {
+        self.pending_spawn_mask & (1u32 << slot_idx) != 0
+    }
+
+    pub fn set_spawn_pending(&mut self, slot_idx: usize, pending: bool) {
+        if pending {
+            self.pending_spawn_mask |= 1u32 << slot_idx;
+        } else {
+            self.pending_spawn_mask &= !(1u32 << slot_idx);
+        }
+    }
+
+    /// Active Pokemon plus spawns still waiting on VRF. Must stay within max_active_pokemon.
+    pub fn occupied_count(&self) -> u32 {
+        self.active_count as u32 + self.pending_spawn_mask.count_ones()
+    }
 }
 
 /// Individual Pokemon spawn data.
@@ -80,6 +240,8 @@ pub struct PokemonSlot {
     pub throw_attempts: u8,
     /// Unix timestamp when this Pokemon was spawned.
     pub spawn_timestamp: i64,
+    /// Rarity tier (RARITY_*). Scales the catch rate.
+    pub rarity: u8,
 }
 
 impl PokemonSlot {
@@ -88,10 +250,12 @@ impl PokemonSlot {
         + 2   // pos_x
         + 2   // pos_y
         + 1   // throw_attempts
-        + 8;  // spawn_timestamp
+        + 8   // spawn_timestamp
+        + 1;  // rarity
 }
 
 /// Per-player ball inventory and lifetime stats.
+/// Fields after `total_catches` were appended after launch (see migrate_player_inventory).
 #[account]
 pub struct PlayerInventory {
     /// Player's wallet pubkey.
@@ -104,6 +268,14 @@ pub struct PlayerInventory {
     pub total_throws: u64,
     /// Lifetime total successful catches.
     pub total_catches: u64,
+    /// Next nonce expected from this player's relayed throw intents.
+    pub relay_nonce: u64,
+    /// Unix timestamp the current refund window opened (first refundable purchase after the last one lapsed).
+    pub refund_window_start: i64,
+    /// Balls bought in the current refund window and still held, per type. Only these can be sold back.
+    pub refundable_balls: [u32; NUM_BALL_TYPES],
+    /// SolBalls paid for `refundable_balls`, per type.
+    pub refundable_cost: [u64; NUM_BALL_TYPES],
     /// PDA bump seed.
     pub bump: u8,
 }
@@ -115,7 +287,169 @@ impl PlayerInventory {
         + 8   // total_purchased
         + 8   // total_throws
         + 8   // total_catches
+        + 8   // relay_nonce
+        + 8   // refund_window_start
+        + (4 * NUM_BALL_TYPES)  // refundable_balls
+        + (8 * NUM_BALL_TYPES)  // refundable_cost
+        + 1;  // bump
+
+    /// Whether `now` is still within `refund_window` of the window opening.
+    pub fn in_refund_window(&self, now: i64, refund_window: i64) -> bool {
+        refund_window > 0 && now <= self.refund_window_start.saturating_add(refund_window)
+    }
+
+    /// Record a SolBalls purchase as refundable and hold its cost back in
+    /// GameConfig.outstanding_refundable. Purchases made while the window is open
+    /// join it without extending it; once it has lapsed, a new one opens.
+    pub fn record_refundable(
+        &mut self,
+        game_config: &mut GameConfig,
+        ball_type: u8,
+        quantity: u32,
+        cost: u64,
+        now: i64,
+    ) -> Result<()> {
+        if !self.in_refund_window(now, game_config.refund_window) {
+            self.clear_refundable(game_config);
+            self.refund_window_start = now;
+        }
+
+        let idx = ball_type as usize;
+        self.refundable_balls[idx] = self.refundable_balls[idx]
+            .checked_add(quantity)
+            .ok_or(GameError::MathOverflow)?;
+        self.refundable_cost[idx] = self.refundable_cost[idx]
+            .checked_add(cost)
+            .ok_or(GameError::MathOverflow)?;
+        game_config.outstanding_refundable = game_config.outstanding_refundable
+            .checked_add(cost)
+            .ok_or(GameError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Drop everything left in the current refund window and release its cost
+    /// from GameConfig.outstanding_refundable. Returns the cost released.
+    pub fn clear_refundable(&mut self, game_config: &mut GameConfig) -> u64 {
+        let cost = self.refundable_cost
+            .iter()
+            .fold(0u64, |total, cost| total.saturating_add(*cost));
+        self.refundable_balls = [0; NUM_BALL_TYPES];
+        self.refundable_cost = [0; NUM_BALL_TYPES];
+        game_config.outstanding_refundable = game_config.outstanding_refundable.saturating_sub(cost);
+        cost
+    }
+
+    /// Take balls out of the inventory. Refundable balls go first, and the
+    /// recorded cost shrinks pro-rata (rounding down) and is released from
+    /// GameConfig.outstanding_refundable. Returns the cost removed.
+    pub fn debit_balls(
+        &mut self,
+        game_config: &mut GameConfig,
+        ball_type: u8,
+        quantity: u32,
+    ) -> Result<u64> {
+        let idx = ball_type as usize;
+        self.balls[idx] = self.balls[idx]
+            .checked_sub(quantity)
+            .ok_or(GameError::InsufficientBalls)?;
+
+        let refundable = self.refundable_balls[idx];
+        if refundable == 0 {
+            return Ok(0);
+        }
+        let removed = quantity.min(refundable);
+        let cost = ((self.refundable_cost[idx] as u128)
+            .checked_mul(removed as u128)
+            .ok_or(GameError::MathOverflow)?
+            / refundable as u128) as u64;
+
+        self.refundable_balls[idx] = refundable
+            .checked_sub(removed)
+            .ok_or(GameError::MathOverflow)?;
+        self.refundable_cost[idx] = self.refundable_cost[idx]
+            .checked_sub(cost)
+            .ok_or(GameError::MathOverflow)?;
+        game_config.outstanding_refundable = game_config.outstanding_refundable.saturating_sub(cost);
+        Ok(cost)
+    }
+}
+
+/// Per-player spend windows and self-exclusion. Created on first purchase
+/// (or self_exclude) and checked by every purchase instruction.
+#[account]
+pub struct SpendTracker {
+    /// Player's wallet pubkey.
+    pub player: Pubkey,
+    /// Unix timestamp the current daily window started.
+    pub day_start: i64,
+    /// Unix timestamp the current weekly window started.
+    pub week_start: i64,
+    /// Amount spent in the current daily window, indexed by CURRENCY_*.
+    pub day_spent: [u64; NUM_SPEND_CURRENCIES],
+    /// Amount spent in the current weekly window, indexed by CURRENCY_*.
+    pub week_spent: [u64; NUM_SPEND_CURRENCIES],
+    /// Purchases are blocked until this Unix timestamp (0 = not excluded).
+    pub excluded_until: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl SpendTracker {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // player
+        + 8   // day_start
+        + 8   // week_start
+        + (8 * NUM_SPEND_CURRENCIES)  // day_spent
+        + (8 * NUM_SPEND_CURRENCIES)  // week_spent
+        + 8   // excluded_until
         + 1;  // bump
+
+    /// Record a purchase of `amount` in `currency` at `now`. A window restarts
+    /// once its full length has elapsed; the purchase fails if the player is
+    /// self-excluded or it would take either window over the configured limit.
+    pub fn record_spend(
+        &mut self,
+        config: &GameConfig,
+        currency: u8,
+        amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(now >= self.excluded_until, GameError::SelfExcluded);
+
+        let idx = currency as usize;
+        require!(idx < NUM_SPEND_CURRENCIES, GameError::InvalidCurrency);
+
+        if now >= self.day_start.saturating_add(SPEND_WINDOW_DAY) {
+            self.day_start = now;
+            self.day_spent = [0; NUM_SPEND_CURRENCIES];
+        }
+        if now >= self.week_start.saturating_add(SPEND_WINDOW_WEEK) {
+            self.week_start = now;
+            self.week_spent = [0; NUM_SPEND_CURRENCIES];
+        }
+
+        let day_spent = self.day_spent[idx]
+            .checked_add(amount)
+            .ok_or(GameError::MathOverflow)?;
+        let week_spent = self.week_spent[idx]
+            .checked_add(amount)
+            .ok_or(GameError::MathOverflow)?;
+
+        let daily_limit = config.daily_spend_limits[idx];
+        require!(
+            daily_limit == 0 || day_spent <= daily_limit,
+            GameError::DailySpendLimitExceeded
+        );
+        let weekly_limit = config.weekly_spend_limits[idx];
+        require!(
+            weekly_limit == 0 || week_spent <= weekly_limit,
+            GameError::WeeklySpendLimitExceeded
+        );
+
+        self.day_spent[idx] = day_spent;
+        self.week_spent[idx] = week_spent;
+        Ok(())
+    }
 }
 
 /// NFT vault tracking which Metaplex NFTs are held.
@@ -144,13 +478,61 @@ impl NftVault {
         + 1;  // bump
 }
 
+/// NFT won by a catch but not yet delivered. Created by consume_randomness,
+/// closed by claim_prize once the NFT reaches the winner's ATA.
+#[account]
+pub struct PendingPrize {
+    /// Player who caught the Pokemon.
+    pub winner: Pubkey,
+    /// Awarded NFT mint (already removed from NftVault.mints).
+    pub nft_mint: Pubkey,
+    /// VrfRequest that produced the catch (PDA seed).
+    pub vrf_request: Pubkey,
+    /// Caught Pokemon.
+    pub pokemon_id: u64,
+    /// Account that paid this prize's rent. Refunded on claim.
+    pub rent_payer: Pubkey,
+    /// Unix timestamp of the catch.
+    pub created_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl PendingPrize {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // winner
+        + 32  // nft_mint
+        + 32  // vrf_request
+        + 8   // pokemon_id
+        + 32  // rent_payer
+        + 8   // created_at
+        + 1;  // bump
+}
+
 /// Treasury configuration for revenue tracking.
+/// Fields after `total_withdrawn` were appended after launch (see migrate_game_config).
 #[account]
 pub struct TreasuryConfig {
-    /// Treasury wallet pubkey.
+    /// Treasury wallet. Only destination for revenue withdrawals and the treasury split.
     pub treasury_wallet: Pubkey,
-    /// Total SolBalls withdrawn for revenue processing.
+    /// Total SolBalls sent to the treasury wallet by withdraw_revenue.
     pub total_withdrawn: u64,
+    /// Total USDC withdrawn for revenue processing.
+    pub total_usdc_withdrawn: u64,
+    /// Total lamports withdrawn from the SOL treasury.
+    pub total_sol_withdrawn: u64,
+    /// Revenue split in basis points, indexed by SPLIT_*. Sums to 10,000.
+    pub split_bps: [u16; NUM_SPLIT_BUCKETS],
+    /// Total SolBalls distributed to the treasury wallet.
+    pub total_to_treasury: u64,
+    /// Total SolBalls distributed to the prize pool PDA.
+    pub total_to_prize_pool: u64,
+    /// Total SolBalls distributed to the reserve PDA.
+    pub total_to_reserve: u64,
+    /// Total SolBalls withdrawn from the prize pool PDA.
+    pub total_from_prize_pool: u64,
+    /// Total SolBalls withdrawn from the reserve PDA.
+    pub total_from_reserve: u64,
     /// PDA bump seed.
     pub bump: u8,
 }
@@ -159,11 +541,27 @@ impl TreasuryConfig {
     pub const LEN: usize = 8  // discriminator
         + 32  // treasury_wallet
         + 8   // total_withdrawn
+        + 8   // total_usdc_withdrawn
+        + 8   // total_sol_withdrawn
+        + (2 * NUM_SPLIT_BUCKETS)  // split_bps
+        + 8   // total_to_treasury
+        + 8   // total_to_prize_pool
+        + 8   // total_to_reserve
+        + 8   // total_from_prize_pool
+        + 8   // total_from_reserve
         + 1;  // bump
+
+    /// Each bucket may be 0, but the table must sum to exactly 10,000 bps.
+    pub fn validate_split(split_bps: &[u16; NUM_SPLIT_BUCKETS]) -> Result<()> {
+        let total: u64 = split_bps.iter().map(|bps| *bps as u64).sum();
+        require!(total == BPS_DENOMINATOR, GameError::InvalidRevenueSplit);
+        Ok(())
+    }
 }
 
 /// Pending VRF request state. Created when VRF is requested,
-/// read during consume_randomness.
+/// closed back to `payer` by consume_randomness.
+/// Fields after `is_fulfilled` were appended after launch (see migrate_vrf_request).
 #[account]
 pub struct VrfRequest {
     /// Request type: 0 = spawn, 1 = throw.
@@ -178,6 +576,14 @@ pub struct VrfRequest {
     pub seed: [u8; 32],
     /// Whether this request has been fulfilled.
     pub is_fulfilled: bool,
+    /// Account that paid this request's rent. Refunded when the request is closed.
+    pub payer: Pubkey,
+    /// Pokemon targeted by a throw (0 for spawns).
+    pub pokemon_id: u64,
+    /// Unix timestamp when the request was made.
+    pub requested_at: i64,
+    /// SessionKey PDA whose allowance paid for a throw (default = not a session throw).
+    pub session: Pubkey,
     /// PDA bump seed.
     pub bump: u8,
 }
@@ -190,5 +596,147 @@ impl VrfRequest {
         + 1   // ball_type
         + 32  // seed
         + 1   // is_fulfilled
+        + 32  // payer
+        + 8   // pokemon_id
+        + 8   // requested_at
+        + 32  // session
+        + 1;  // bump
+}
+
+/// Timelocked configuration values. Queued in a PendingConfigChange and
+/// applied to GameConfig by execute_config_change once the eta has passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct ConfigValues {
+    /// Ball prices in SolBalls atomic units: [poke, great, ultra, master].
+    pub ball_prices: [u64; 4],
+    /// Catch rates 0-100 percent: [poke, great, ultra, master].
+    pub catch_rates: [u8; 4],
+    /// Soft cap on active Pokemon (1-20).
+    pub max_active_pokemon: u8,
+    /// Ball prices in USDC atomic units. 0 = tier not sold for USDC.
+    pub usdc_ball_prices: [u64; 4],
+    /// Ball prices in lamports. 0 = tier not sold for SOL.
+    pub sol_ball_prices: [u64; 4],
+    /// Ball prices in USD micro-units. 0 = tier not sold at the oracle price.
+    pub usd_ball_prices: [u64; 4],
+    /// VRF spawn odds per rarity in basis points. Must sum to 10,000.
+    pub rarity_weights: [u16; NUM_RARITIES],
+    /// Catch-rate multiplier per rarity in basis points (1-20,000).
+    pub rarity_catch_multipliers: [u16; NUM_RARITIES],
+    /// Pyth SolBalls/USD price account for oracle-priced purchases (default = none).
+    pub price_feed: Pubkey,
+    /// Maximum oracle price age in seconds (1 to MAX_PRICE_AGE).
+    pub max_price_age: i64,
+    /// Maximum oracle confidence interval in basis points of the price (1-10,000).
+    pub max_price_conf_bps: u16,
+    /// Sell-back window in seconds (0 disables refunds, max MAX_REFUND_WINDOW).
+    pub refund_window: i64,
+    /// Share of the price kept on each refund, in basis points (0-10,000).
+    pub refund_haircut_bps: u16,
+}
+
+impl ConfigValues {
+    pub const LEN: usize = (8 * 4)  // ball_prices
+        + NUM_BALL_TYPES  // catch_rates
+        + 1   // max_active_pokemon
+        + (8 * 4)  // usdc_ball_prices
+        + (8 * 4)  // sol_ball_prices
+        + (8 * 4)  // usd_ball_prices
+        + (2 * NUM_RARITIES)  // rarity_weights
+        + (2 * NUM_RARITIES)  // rarity_catch_multipliers
+        + 32  // price_feed
+        + 8   // max_price_age
+        + 2   // max_price_conf_bps
+        + 8   // refund_window
+        + 2;  // refund_haircut_bps
+
+    pub fn validate(&self) -> Result<()> {
+        for price in self.ball_prices.iter() {
+            require!(*price > 0, GameError::ZeroBallPrice);
+        }
+        for rate in self.catch_rates.iter() {
+            require!(*rate <= 100, GameError::InvalidCatchRate);
+        }
+        require!(
+            self.max_active_pokemon >= 1 && self.max_active_pokemon <= MAX_POKEMON_SLOTS as u8,
+            GameError::InvalidMaxActivePokemon
+        );
+        let total_weight: u64 = self.rarity_weights.iter().map(|w| *w as u64).sum();
+        require!(total_weight == BPS_DENOMINATOR, GameError::InvalidRarityWeights);
+        for multiplier in self.rarity_catch_multipliers.iter() {
+            require!(
+                *multiplier > 0 && *multiplier <= MAX_RARITY_CATCH_MULTIPLIER,
+                GameError::InvalidRarityMultiplier
+            );
+        }
+        require!(
+            (1..=MAX_PRICE_AGE).contains(&self.max_price_age),
+            GameError::InvalidPriceBounds
+        );
+        require!(
+            self.max_price_conf_bps > 0 && self.max_price_conf_bps as u64 <= BPS_DENOMINATOR,
+            GameError::InvalidPriceBounds
+        );
+        require!(
+            (0..=MAX_REFUND_WINDOW).contains(&self.refund_window),
+            GameError::InvalidRefundPolicy
+        );
+        require!(
+            self.refund_haircut_bps as u64 <= BPS_DENOMINATOR,
+            GameError::InvalidRefundPolicy
+        );
+        Ok(())
+    }
+}
+
+/// A queued configuration change. Only one can be pending at a time.
+#[account]
+pub struct PendingConfigChange {
+    /// Who queued the change (receives rent back when executed or cancelled).
+    pub proposer: Pubkey,
+    /// New values to apply.
+    pub values: ConfigValues,
+    /// Unix timestamp when the change was queued.
+    pub queued_at: i64,
+    /// Unix timestamp after which the change can be executed.
+    pub eta: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl PendingConfigChange {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // proposer
+        + ConfigValues::LEN  // values
+        + 8   // queued_at
+        + 8   // eta
+        + 1;  // bump
+}
+
+/// Ephemeral key a player has authorized to act on their behalf.
+/// Seeds: [SESSION_SEED, owner, session_key]
+#[account]
+pub struct SessionKey {
+    /// Player wallet that created the session.
+    pub owner: Pubkey,
+    /// Ephemeral pubkey allowed to sign in place of the owner.
+    pub session_key: Pubkey,
+    /// Unix timestamp after which the session can no longer be used.
+    pub expires_at: i64,
+    /// Remaining balls the session may throw, per ball type.
+    pub ball_allowance: [u32; 4],
+    /// Bitmask of SESSION_ALLOW_* permissions.
+    pub allowed_mask: u8,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl SessionKey {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // owner
+        + 32  // session_key
+        + 8   // expires_at
+        + (4 * 4)  // ball_allowance
+        + 1   // allowed_mask
         + 1;  // bump
 }
@@ -14,6 +14,7 @@ import {
   formatTokenAmount,
   formatPokemonSlot,
   BALL_NAMES,
+  RARITY_NAMES,
   GAME_SOLBALLS_SEED,
 } from "./common";
 
@@ -41,7 +42,6 @@ async function main() {
     console.log("=== Game Config ===");
     console.log(`  Initialized:        ${gameConfig.isInitialized}`);
     console.log(`  Authority:          ${gameConfig.authority.toBase58()}`);
-    console.log(`  Treasury:           ${gameConfig.treasury.toBase58()}`);
     console.log(`  SolBalls Mint:      ${gameConfig.solballsMint.toBase58()}`);
     console.log(`  USDC Mint:          ${gameConfig.usdcMint.toBase58()}`);
     console.log(`  Max Active Pokemon: ${gameConfig.maxActivePokemon}`);
@@ -97,10 +97,12 @@ async function main() {
       if (slot.isActive) {
         const ts = new Date(Number(slot.spawnTimestamp) * 1000);
         console.log(
-          `  Slot ${String(i).padStart(2)}: Pokemon #${slot.pokemonId} ` +
+          `  Slot ${String(i).padStart(2)}: ${RARITY_NAMES[slot.rarity]} Pokemon #${slot.pokemonId} ` +
           `at (${slot.posX}, ${slot.posY}) — ${slot.throwAttempts}/3 attempts — ` +
           `spawned ${ts.toISOString()}`
         );
+      } else if ((pokemonSlots.pendingSpawnMask >>> i) & 1) {
+        console.log(`  Slot ${String(i).padStart(2)}: (VRF spawn pending)`);
       }
     }
 
@@ -22,6 +22,10 @@ export const NFT_VAULT_SEED = Buffer.from("nft_vault");
 export const TREASURY_SEED = Buffer.from("treasury");
 export const VRF_REQ_SEED = Buffer.from("vrf_req");
 export const GAME_SOLBALLS_SEED = Buffer.from("game_solballs");
+export const PENDING_CONFIG_SEED = Buffer.from("pending_config");
+export const SOL_TREASURY_SEED = Buffer.from("sol_treasury");
+export const PRIZE_POOL_SEED = Buffer.from("prize_pool");
+export const RESERVE_SEED = Buffer.from("reserve");
 
 // ORAO VRF
 export const ORAO_VRF_PROGRAM_ID = new PublicKey(
@@ -33,6 +37,10 @@ export const ORAO_RANDOMNESS_SEED = Buffer.from("orao-vrf-randomness-request");
 // Ball type names
 export const BALL_NAMES = ["Poke Ball", "Great Ball", "Ultra Ball", "Master Ball"];
 
+// Pokemon rarity names, indexed by rarity (must match program)
+export const RARITY_NAMES = ["Common", "Uncommon", "Rare", "Legendary"];
+export const RARITY_COMMON = 0;
+
 /**
  * Load the Anchor program and provider from environment.
  * Reads wallet from Anchor.toml or ANCHOR_WALLET env var.
@@ -71,8 +79,20 @@ export function deriveGamePDAs(programId: PublicKey) {
     [TREASURY_SEED],
     programId
   );
+  const [solTreasury] = PublicKey.findProgramAddressSync(
+    [SOL_TREASURY_SEED],
+    programId
+  );
+  const [prizePool] = PublicKey.findProgramAddressSync(
+    [PRIZE_POOL_SEED],
+    programId
+  );
+  const [reserve] = PublicKey.findProgramAddressSync(
+    [RESERVE_SEED],
+    programId
+  );
 
-  return { gameConfig, pokemonSlots, nftVault, treasuryConfig };
+  return { gameConfig, pokemonSlots, nftVault, treasuryConfig, solTreasury, prizePool, reserve };
 }
 
 /**
@@ -118,5 +138,5 @@ export function formatPokemonSlot(slot: any, index: number): string {
   if (!slot.isActive) {
     return `  Slot ${index}: (empty)`;
   }
-  return `  Slot ${index}: Pokemon #${slot.pokemonId} at (${slot.posX}, ${slot.posY}) — ${slot.throwAttempts}/3 attempts`;
+  return `  Slot ${index}: ${RARITY_NAMES[slot.rarity]} Pokemon #${slot.pokemonId} at (${slot.posX}, ${slot.posY}) — ${slot.throwAttempts}/3 attempts`;
 }
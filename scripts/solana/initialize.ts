@@ -95,6 +95,7 @@ async function main() {
         pokemonSlots: pdas.pokemonSlots,
         nftVault: pdas.nftVault,
         treasuryConfig: pdas.treasuryConfig,
+        solTreasury: pdas.solTreasury,
         solballsMint: solballsMintPubkey,
         gameSolballsAccount: gameSolballsAta,
         systemProgram: SystemProgram.programId,
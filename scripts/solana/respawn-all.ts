@@ -16,7 +16,7 @@
  *   npx tsx scripts/solana/respawn-all.ts --count 20      # Respawn first N active slots
  */
 import * as anchor from "@coral-xyz/anchor";
-import { loadProgram, deriveGamePDAs } from "./common.js";
+import { loadProgram, deriveGamePDAs, RARITY_COMMON } from "./common.js";
 
 async function main() {
   const args = process.argv.slice(2);
@@ -99,7 +99,7 @@ async function main() {
     try {
       console.log(`  Spawning at (${posX}, ${posY})...`);
       const spawnTx = await program.methods
-        .forceSpawnPokemon(slotIndex, posX, posY)
+        .forceSpawnPokemon(slotIndex, posX, posY, RARITY_COMMON)
         .accounts({
           authority,
           gameConfig: pdas.gameConfig,
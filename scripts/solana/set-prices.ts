@@ -1,5 +1,8 @@
 /**
- * Update ball prices and catch rates.
+ * Queue ball price, catch rate and max-active changes.
+ *
+ * Changes are timelocked: this script queues a PendingConfigChange which can
+ * be applied with --execute once GameConfig.config_change_delay has elapsed.
  *
  * Usage:
  *   # Set a single ball price (SOLCATCH, 9 decimals):
@@ -16,8 +19,22 @@
  *
  *   # Set max active Pokemon (1-20):
  *   npx ts-node scripts/solana/set-prices.ts --max-pokemon 10
+ *
+ *   # Apply the queued change once its ETA has passed:
+ *   npx ts-node scripts/solana/set-prices.ts --execute
+ *
+ *   # Cancel the queued change:
+ *   npx ts-node scripts/solana/set-prices.ts --cancel
  */
-import { loadProgram, deriveGamePDAs, BALL_NAMES, formatTokenAmount } from "./common";
+import { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  loadProgram,
+  deriveGamePDAs,
+  BALL_NAMES,
+  formatTokenAmount,
+  PENDING_CONFIG_SEED,
+} from "./common";
 
 interface PriceUpdate {
   ballType: number;
@@ -35,6 +52,8 @@ async function main() {
   const priceUpdates: PriceUpdate[] = [];
   const rateUpdates: RateUpdate[] = [];
   let maxPokemon: number | undefined;
+  let execute = false;
+  let cancel = false;
 
   for (let i = 0; i < args.length; i++) {
     if (args[i] === "--ball-price" && args[i + 1] && args[i + 2]) {
@@ -72,9 +91,21 @@ async function main() {
     if (args[i] === "--max-pokemon" && args[i + 1]) {
       maxPokemon = parseInt(args[++i]);
     }
+    if (args[i] === "--execute") {
+      execute = true;
+    }
+    if (args[i] === "--cancel") {
+      cancel = true;
+    }
   }
 
-  if (priceUpdates.length === 0 && rateUpdates.length === 0 && maxPokemon === undefined) {
+  if (
+    priceUpdates.length === 0 &&
+    rateUpdates.length === 0 &&
+    maxPokemon === undefined &&
+    !execute &&
+    !cancel
+  ) {
     console.error(
       "Usage:\n" +
       "  npx ts-node scripts/solana/set-prices.ts --ball-price <type> <price>\n" +
@@ -82,6 +113,8 @@ async function main() {
       "  npx ts-node scripts/solana/set-prices.ts --all-prices <p0>,<p1>,<p2>,<p3>\n" +
       "  npx ts-node scripts/solana/set-prices.ts --all-rates <r0>,<r1>,<r2>,<r3>\n" +
       "  npx ts-node scripts/solana/set-prices.ts --max-pokemon <1-20>\n" +
+      "  npx ts-node scripts/solana/set-prices.ts --execute\n" +
+      "  npx ts-node scripts/solana/set-prices.ts --cancel\n" +
       "\n" +
       "Ball types: 0=Poke, 1=Great, 2=Ultra, 3=Master\n" +
       "Prices are in SOLCATCH atomic units (9 decimals, e.g. 1000000000 = 1.0 SOLCATCH)\n" +
@@ -104,84 +137,104 @@ async function main() {
   console.log(`  Max Active Pokemon: ${gameConfig.maxActivePokemon}`);
   console.log("");
 
-  // Apply price updates
-  for (const update of priceUpdates) {
-    if (update.ballType < 0 || update.ballType > 3) {
-      console.error(`  [SKIP] Invalid ball type: ${update.ballType}`);
-      continue;
-    }
-    if (update.price <= 0) {
-      console.error(`  [SKIP] Price must be > 0`);
-      continue;
-    }
-
-    try {
-      const tx = await program.methods
-        .setBallPrice(update.ballType, new (await import("@coral-xyz/anchor")).BN(update.price))
-        .accounts({
-          authority,
-          gameConfig: pdas.gameConfig,
-        })
-        .rpc();
+  const [pendingConfig] = PublicKey.findProgramAddressSync(
+    [PENDING_CONFIG_SEED],
+    program.programId
+  );
+
+  if (cancel) {
+    const pending = await program.account.pendingConfigChange.fetch(pendingConfig);
+    const tx = await program.methods
+      .cancelConfigChange()
+      .accounts({
+        authority,
+        gameConfig: pdas.gameConfig,
+        pendingConfigChange: pendingConfig,
+        proposer: pending.proposer,
+      })
+      .rpc();
+    console.log(`  [OK] Queued config change cancelled — TX: ${tx}`);
+    return;
+  }
 
+  if (execute) {
+    const pending = await program.account.pendingConfigChange.fetch(pendingConfig);
+    const tx = await program.methods
+      .executeConfigChange()
+      .accounts({
+        executor: authority,
+        gameConfig: pdas.gameConfig,
+        pendingConfigChange: pendingConfig,
+        proposer: pending.proposer,
+      })
+      .rpc();
+    console.log(`  [OK] Queued config change executed — TX: ${tx}`);
+  } else {
+    // Merge updates into the current values and queue them as one change
+    const values = {
+      ballPrices: [...gameConfig.ballPrices],
+      catchRates: [...gameConfig.catchRates],
+      maxActivePokemon: gameConfig.maxActivePokemon,
+      usdcBallPrices: [...gameConfig.usdcBallPrices],
+      solBallPrices: [...gameConfig.solBallPrices],
+      usdBallPrices: [...gameConfig.usdBallPrices],
+      rarityWeights: [...gameConfig.rarityWeights],
+      rarityCatchMultipliers: [...gameConfig.rarityCatchMultipliers],
+    };
+
+    for (const update of priceUpdates) {
+      if (update.ballType < 0 || update.ballType > 3) {
+        console.error(`  [SKIP] Invalid ball type: ${update.ballType}`);
+        continue;
+      }
+      if (update.price <= 0) {
+        console.error(`  [SKIP] Price must be > 0`);
+        continue;
+      }
+      values.ballPrices[update.ballType] = new BN(update.price);
       console.log(
-        `  [OK] ${BALL_NAMES[update.ballType]} price → ${formatTokenAmount(update.price)} SolBalls — TX: ${tx}`
-      );
-    } catch (err: any) {
-      console.error(
-        `  [FAIL] ${BALL_NAMES[update.ballType]} price → ${err.message || err}`
+        `  ${BALL_NAMES[update.ballType]} price → ${formatTokenAmount(update.price)} SolBalls`
       );
     }
-  }
 
-  // Apply rate updates
-  for (const update of rateUpdates) {
-    if (update.ballType < 0 || update.ballType > 3) {
-      console.error(`  [SKIP] Invalid ball type: ${update.ballType}`);
-      continue;
+    for (const update of rateUpdates) {
+      if (update.ballType < 0 || update.ballType > 3) {
+        console.error(`  [SKIP] Invalid ball type: ${update.ballType}`);
+        continue;
+      }
+      if (update.rate < 0 || update.rate > 100) {
+        console.error(`  [SKIP] Catch rate must be 0-100`);
+        continue;
+      }
+      values.catchRates[update.ballType] = update.rate;
+      console.log(`  ${BALL_NAMES[update.ballType]} catch rate → ${update.rate}%`);
     }
-    if (update.rate < 0 || update.rate > 100) {
-      console.error(`  [SKIP] Catch rate must be 0-100`);
-      continue;
+
+    if (maxPokemon !== undefined) {
+      if (maxPokemon < 1 || maxPokemon > 20) {
+        console.error(`  [SKIP] Max active Pokemon must be 1-20`);
+      } else {
+        values.maxActivePokemon = maxPokemon;
+        console.log(`  Max active Pokemon → ${maxPokemon}`);
+      }
     }
 
     try {
       const tx = await program.methods
-        .setCatchRate(update.ballType, update.rate)
+        .queueConfigChange(values)
         .accounts({
           authority,
           gameConfig: pdas.gameConfig,
+          pendingConfigChange: pendingConfig,
         })
         .rpc();
 
+      const pending = await program.account.pendingConfigChange.fetch(pendingConfig);
       console.log(
-        `  [OK] ${BALL_NAMES[update.ballType]} catch rate → ${update.rate}% — TX: ${tx}`
+        `  [OK] Config change queued, executable after ${new Date(pending.eta.toNumber() * 1000).toISOString()} — TX: ${tx}`
       );
     } catch (err: any) {
-      console.error(
-        `  [FAIL] ${BALL_NAMES[update.ballType]} catch rate → ${err.message || err}`
-      );
-    }
-  }
-
-  // Apply max active pokemon
-  if (maxPokemon !== undefined) {
-    if (maxPokemon < 1 || maxPokemon > 20) {
-      console.error(`  [SKIP] Max active Pokemon must be 1-20`);
-    } else {
-      try {
-        const tx = await program.methods
-          .setMaxActivePokemon(maxPokemon)
-          .accounts({
-            authority,
-            gameConfig: pdas.gameConfig,
-          })
-          .rpc();
-
-        console.log(`  [OK] Max active Pokemon → ${maxPokemon} — TX: ${tx}`);
-      } catch (err: any) {
-        console.error(`  [FAIL] Max active Pokemon → ${err.message || err}`);
-      }
+      console.error(`  [FAIL] Queue config change → ${err.message || err}`);
     }
   }
 
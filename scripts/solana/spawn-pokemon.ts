@@ -5,8 +5,8 @@
  *   # VRF spawn (random position via ORAO VRF):
  *   npx ts-node scripts/solana/spawn-pokemon.ts --slot 0
  *
- *   # Force spawn (specific position, no VRF):
- *   npx ts-node scripts/solana/spawn-pokemon.ts --slot 0 --pos 500,500
+ *   # Force spawn (specific position, no VRF; rarity 0-3, default common):
+ *   npx ts-node scripts/solana/spawn-pokemon.ts --slot 0 --pos 500,500 [--rarity 3]
  *
  *   # Batch force-spawn multiple slots:
  *   npx ts-node scripts/solana/spawn-pokemon.ts --batch 0,1,2,3,4
@@ -21,6 +21,8 @@ import {
   ORAO_CONFIG_SEED,
   ORAO_RANDOMNESS_SEED,
   VRF_REQ_SEED,
+  RARITY_NAMES,
+  RARITY_COMMON,
 } from "./common";
 
 // ============================================================
@@ -59,6 +61,7 @@ async function main() {
   let posX: number | undefined;
   let posY: number | undefined;
   let batchSlots: number[] | undefined;
+  let rarity = RARITY_COMMON;
 
   for (let i = 0; i < args.length; i++) {
     if (args[i] === "--slot" && args[i + 1]) slotIndex = parseInt(args[++i]);
@@ -67,6 +70,7 @@ async function main() {
       posX = x;
       posY = y;
     }
+    if (args[i] === "--rarity" && args[i + 1]) rarity = parseInt(args[++i]);
     if (args[i] === "--batch" && args[i + 1]) {
       batchSlots = args[++i].split(",").map(Number);
     }
@@ -75,7 +79,7 @@ async function main() {
   if (slotIndex === undefined && !batchSlots) {
     console.error(
       "Usage:\n" +
-      "  npx ts-node scripts/solana/spawn-pokemon.ts --slot <0-19> [--pos <x>,<y>]\n" +
+      "  npx ts-node scripts/solana/spawn-pokemon.ts --slot <0-19> [--pos <x>,<y>] [--rarity <0-3>]\n" +
       "  npx ts-node scripts/solana/spawn-pokemon.ts --batch <slot1,slot2,...>"
     );
     process.exit(1);
@@ -99,7 +103,7 @@ async function main() {
 
       try {
         const tx = await program.methods
-          .forceSpawnPokemon(slot, x, y)
+          .forceSpawnPokemon(slot, x, y, rarity)
           .accounts({
             authority,
             gameConfig: pdas.gameConfig,
@@ -117,10 +121,10 @@ async function main() {
   // Single spawn
   if (posX !== undefined && posY !== undefined) {
     // Force spawn at specific coordinates
-    console.log(`=== Force Spawn: slot ${slotIndex} at (${posX}, ${posY}) ===`);
+    console.log(`=== Force Spawn: ${RARITY_NAMES[rarity]} in slot ${slotIndex} at (${posX}, ${posY}) ===`);
     try {
       const tx = await program.methods
-        .forceSpawnPokemon(slotIndex!, posX, posY)
+        .forceSpawnPokemon(slotIndex!, posX, posY, rarity)
         .accounts({
           authority,
           gameConfig: pdas.gameConfig,
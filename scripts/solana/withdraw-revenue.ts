@@ -1,5 +1,7 @@
 /**
- * Withdraw SolBalls revenue from the game program.
+ * Withdraw SolBalls revenue from the game program through the revenue split.
+ * Only the treasury share goes to the treasury wallet; the rest lands in the
+ * prize pool and reserve PDAs (see withdraw_prize_pool / withdraw_reserve).
  *
  * Usage:
  *   # Withdraw specific amount (in SolBalls atomic units):
@@ -12,6 +14,7 @@
  *   npx ts-node scripts/solana/withdraw-revenue.ts --status
  */
 import BN from "bn.js";
+import { SystemProgram } from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
 import { loadProgram, deriveGamePDAs, formatTokenAmount } from "./common";
 
@@ -55,9 +58,11 @@ async function main() {
     pdas.gameConfig,
     true // allowOwnerOffCurve for PDA
   );
-  const authoritySolballsAta = await getAssociatedTokenAddress(
+  // Read treasury config (withdrawals only go to the treasury wallet)
+  const treasuryConfig = await program.account.treasuryConfig.fetch(pdas.treasuryConfig);
+  const treasurySolballsAta = await getAssociatedTokenAddress(
     solballsMint,
-    authority
+    treasuryConfig.treasuryWallet
   );
 
   // Get current balance
@@ -68,15 +73,16 @@ async function main() {
   } catch {
     gameBalance = 0;
   }
-
-  // Read treasury config
-  const treasuryConfig = await program.account.treasuryConfig.fetch(pdas.treasuryConfig);
+  // SolBalls still refundable through sell_back_balls cannot be withdrawn
+  const heldForRefunds = gameConfig.outstandingRefundable.toNumber();
+  const available = Math.max(gameBalance - heldForRefunds, 0);
 
   console.log("=== Revenue Status ===");
   console.log(`  Authority:         ${authority.toBase58()}`);
   console.log(`  SolBalls Mint:     ${solballsMint.toBase58()}`);
   console.log(`  Game ATA:          ${gameSolballsAta.toBase58()}`);
   console.log(`  Game Balance:      ${formatTokenAmount(gameBalance)} SolBalls`);
+  console.log(`  Held for Refunds:  ${formatTokenAmount(heldForRefunds)} SolBalls`);
   console.log(`  Total Revenue:     ${formatTokenAmount(gameConfig.totalRevenue)} SolBalls`);
   console.log(`  Total Withdrawn:   ${formatTokenAmount(treasuryConfig.totalWithdrawn)} SolBalls`);
   console.log(`  Treasury Wallet:   ${treasuryConfig.treasuryWallet.toBase58()}`);
@@ -90,7 +96,7 @@ async function main() {
   // Determine withdrawal amount
   let withdrawAmount: number;
   if (withdrawAll) {
-    withdrawAmount = gameBalance;
+    withdrawAmount = available;
     console.log(`Withdrawing ALL: ${formatTokenAmount(withdrawAmount)} SolBalls`);
   } else {
     withdrawAmount = amount!;
@@ -98,26 +104,26 @@ async function main() {
   }
 
   if (withdrawAmount <= 0) {
-    console.log("Nothing to withdraw (balance is 0).");
+    console.log("Nothing to withdraw (no balance beyond refundable SolBalls).");
     return;
   }
 
-  if (withdrawAmount > gameBalance) {
+  if (withdrawAmount > available) {
     console.error(
       `Error: Requested ${formatTokenAmount(withdrawAmount)} SolBalls ` +
-      `but only ${formatTokenAmount(gameBalance)} available.`
+      `but only ${formatTokenAmount(available)} available.`
     );
     process.exit(1);
   }
 
-  // Check authority has a SolBalls ATA
+  // Check treasury wallet has a SolBalls ATA
   try {
-    await provider.connection.getTokenAccountBalance(authoritySolballsAta);
+    await provider.connection.getTokenAccountBalance(treasurySolballsAta);
   } catch {
     console.error(
-      "Error: Authority does not have a SolBalls token account.\n" +
-      `  Expected ATA: ${authoritySolballsAta.toBase58()}\n` +
-      "  Create one first with: spl-token create-account <SOLBALLS_MINT>"
+      "Error: Treasury wallet does not have a SolBalls token account.\n" +
+      `  Expected ATA: ${treasurySolballsAta.toBase58()}\n` +
+      "  Create one first with: spl-token create-account <SOLBALLS_MINT> --owner <TREASURY_WALLET> --fee-payer <KEYPAIR>"
     );
     process.exit(1);
   }
@@ -129,13 +135,17 @@ async function main() {
         authority,
         gameConfig: pdas.gameConfig,
         treasuryConfig: pdas.treasuryConfig,
+        solballsMint,
         gameSolballsAccount: gameSolballsAta,
-        authoritySolballsAccount: authoritySolballsAta,
+        treasurySolballsAccount: treasurySolballsAta,
+        prizePool: pdas.prizePool,
+        reserve: pdas.reserve,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .rpc();
 
-    console.log(`\n  [OK] Withdrawn ${formatTokenAmount(withdrawAmount)} SolBalls — TX: ${tx}`);
+    console.log(`\n  [OK] Split ${formatTokenAmount(withdrawAmount)} SolBalls — TX: ${tx}`);
   } catch (err: any) {
     console.error(`\n  [FAIL] Withdrawal failed: ${err.message || err}`);
     process.exit(1);
@@ -75,6 +75,7 @@ export {
   usePokemonSpawnedEvents,
   usePokemonRelocatedEvents,
   useThrowAttemptedEvents,
+  useThrowVoidedEvents,
   useAllGameEvents,
   type SolanaEvent,
   type SolanaEventName,
@@ -85,6 +86,7 @@ export {
   type PokemonDespawnedArgs,
   type PokemonRelocatedArgs,
   type ThrowAttemptedArgs,
+  type ThrowVoidedArgs,
   type NftAwardedArgs,
   type EventArgsMap,
   type UseSolanaEventsReturn,
@@ -21,6 +21,7 @@ export interface BallPurchasedArgs {
   ballType: number;
   quantity: number;
   totalCost: bigint;
+  currency: number; // 0 = SolBalls, 1 = USDC, 2 = SOL
 }
 
 export interface CaughtPokemonArgs {
@@ -28,6 +29,7 @@ export interface CaughtPokemonArgs {
   pokemonId: bigint;
   slotIndex: number;
   nftMint: string;
+  rarity: number;
 }
 
 export interface FailedCatchArgs {
@@ -42,6 +44,7 @@ export interface PokemonSpawnedArgs {
   slotIndex: number;
   posX: number;
   posY: number;
+  rarity: number;
 }
 
 export interface PokemonDespawnedArgs {
@@ -66,6 +69,14 @@ export interface ThrowAttemptedArgs {
   vrfSeed: number[];
 }
 
+export interface ThrowVoidedArgs {
+  thrower: string;
+  pokemonId: bigint;
+  slotIndex: number;
+  ballType: number;
+  currentPokemonId: bigint;
+}
+
 export interface NftAwardedArgs {
   winner: string;
   nftMint: string;
@@ -80,6 +91,7 @@ export type SolanaEventName =
   | 'PokemonDespawned'
   | 'PokemonRelocated'
   | 'ThrowAttempted'
+  | 'ThrowVoided'
   | 'NftAwarded';
 
 export type EventArgsMap = {
@@ -90,6 +102,7 @@ export type EventArgsMap = {
   PokemonDespawned: PokemonDespawnedArgs;
   PokemonRelocated: PokemonRelocatedArgs;
   ThrowAttempted: ThrowAttemptedArgs;
+  ThrowVoided: ThrowVoidedArgs;
   NftAwarded: NftAwardedArgs;
 };
 
@@ -124,6 +137,7 @@ const ANCHOR_EVENT_NAMES: Record<SolanaEventName, string> = {
   PokemonDespawned: 'pokemonDespawned',
   PokemonRelocated: 'pokemonRelocated',
   ThrowAttempted: 'throwAttempted',
+  ThrowVoided: 'throwVoided',
   NftAwarded: 'nftAwarded',
 };
 
@@ -142,6 +156,7 @@ function normalizeEventArgs<T extends SolanaEventName>(
         ballType: rawArgs.ballType ?? 0,
         quantity: rawArgs.quantity ?? 0,
         totalCost: BigInt(rawArgs.totalCost?.toString() ?? '0'),
+        currency: rawArgs.currency ?? 0,
       } as EventArgsMap[T];
 
     case 'CaughtPokemon':
@@ -150,6 +165,7 @@ function normalizeEventArgs<T extends SolanaEventName>(
         pokemonId: BigInt(rawArgs.pokemonId?.toString() ?? '0'),
         slotIndex: rawArgs.slotIndex ?? 0,
         nftMint: rawArgs.nftMint?.toBase58?.() ?? rawArgs.nftMint?.toString() ?? '',
+        rarity: rawArgs.rarity ?? 0,
       } as EventArgsMap[T];
 
     case 'FailedCatch':
@@ -166,6 +182,7 @@ function normalizeEventArgs<T extends SolanaEventName>(
         slotIndex: rawArgs.slotIndex ?? 0,
         posX: rawArgs.posX ?? 0,
         posY: rawArgs.posY ?? 0,
+        rarity: rawArgs.rarity ?? 0,
       } as EventArgsMap[T];
 
     case 'PokemonDespawned':
@@ -193,6 +210,15 @@ function normalizeEventArgs<T extends SolanaEventName>(
         vrfSeed: rawArgs.vrfSeed ?? [],
       } as EventArgsMap[T];
 
+    case 'ThrowVoided':
+      return {
+        thrower: rawArgs.thrower?.toBase58?.() ?? rawArgs.thrower?.toString() ?? '',
+        pokemonId: BigInt(rawArgs.pokemonId?.toString() ?? '0'),
+        slotIndex: rawArgs.slotIndex ?? 0,
+        ballType: rawArgs.ballType ?? 0,
+        currentPokemonId: BigInt(rawArgs.currentPokemonId?.toString() ?? '0'),
+      } as EventArgsMap[T];
+
     case 'NftAwarded':
       return {
         winner: rawArgs.winner?.toBase58?.() ?? rawArgs.winner?.toString() ?? '',
@@ -328,6 +354,12 @@ export function useThrowAttemptedEvents(
   return useSolanaEvents('ThrowAttempted', onThrow);
 }
 
+export function useThrowVoidedEvents(
+  onVoid?: (event: SolanaEvent<'ThrowVoided'>) => void
+) {
+  return useSolanaEvents('ThrowVoided', onVoid);
+}
+
 export function usePokemonRelocatedEvents(
   onRelocate?: (event: SolanaEvent<'PokemonRelocated'>) => void
 ) {
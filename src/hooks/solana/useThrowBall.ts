@@ -20,6 +20,7 @@ import type { Connection } from '@solana/web3.js';
 import {
   throwBall as throwBallTx,
   consumeRandomnessWithRetry,
+  claimPrize,
 } from '../../solana/programClient';
 import { MAX_POKEMON_SLOTS } from '../../solana/constants';
 import type { BallType } from '../../solana/constants';
@@ -290,6 +291,16 @@ export function useThrowBall(): UseThrowBallReturn {
 
         if (cancelledRef.current) return false;
 
+        // ---- Step 5: Deliver the NFT for a catch ----
+        // The catch only records a PendingPrize; if this fails the prize stays claimable.
+        if (parsedResult.status === 'caught') {
+          try {
+            await claimPrize(connection, anchorWallet, throwResult.vrfRequestPDA);
+          } catch (claimErr) {
+            console.error('[useThrowBall] claim_prize failed, prize remains claimable:', claimErr);
+          }
+        }
+
         setLastResult(parsedResult);
         setThrowStatus(parsedResult.status === 'caught' ? 'caught' : parsedResult.status === 'relocated' ? 'relocated' : 'missed');
         setIsLoading(false);
@@ -41,6 +41,8 @@ export const NFT_VAULT_SEED = 'nft_vault';
 export const TREASURY_SEED = 'treasury';
 export const VRF_REQ_SEED = 'vrf_req';
 export const GAME_SOLBALLS_SEED = 'game_solballs';
+export const PENDING_PRIZE_SEED = 'pending_prize';
+export const SPEND_TRACKER_SEED = 'spend_tracker';
 
 // ============================================================
 // GAME CONSTANTS
@@ -126,6 +128,13 @@ export function getPlayerInventoryPDA(player: PublicKey): [PublicKey, number] {
   );
 }
 
+export function getSpendTrackerPDA(player: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SPEND_TRACKER_SEED), player.toBuffer()],
+    POKEBALL_GAME_PROGRAM_ID
+  );
+}
+
 export function getNftVaultPDA(): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from(NFT_VAULT_SEED)],
@@ -146,3 +155,10 @@ export function getVrfRequestPDA(seed: Buffer): [PublicKey, number] {
     POKEBALL_GAME_PROGRAM_ID
   );
 }
+
+export function getPendingPrizePDA(vrfRequest: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PENDING_PRIZE_SEED), vrfRequest.toBuffer()],
+    POKEBALL_GAME_PROGRAM_ID
+  );
+}
@@ -12,13 +12,11 @@ import {
   type TransactionSignature,
   SystemProgram,
   Transaction,
-  VersionedTransaction,
-  TransactionMessage,
 } from '@solana/web3.js';
 import {
   getAssociatedTokenAddress,
-  createAssociatedTokenAccountInstruction,
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from '@solana/spl-token';
 import type { AnchorWallet } from '@solana/wallet-adapter-react';
 import {
@@ -28,8 +26,10 @@ import {
   getGameConfigPDA,
   getPokemonSlotsPDA,
   getPlayerInventoryPDA,
+  getSpendTrackerPDA,
   getNftVaultPDA,
   getTreasuryConfigPDA,
+  getPendingPrizePDA,
   type BallType,
 } from './constants';
 
@@ -43,10 +43,11 @@ import idlJson from '../../target/idl/pokeball_game.json';
 /** Parsed GameConfig account data */
 export interface GameConfig {
   authority: PublicKey;
-  treasury: PublicKey;
   solballsMint: PublicKey;
   usdcMint: PublicKey;
   ballPrices: BN[];
+  usdBallPrices: BN[];
+  priceFeed: PublicKey;
   catchRates: number[];
   maxActivePokemon: number;
   pokemonIdCounter: BN;
@@ -64,6 +65,8 @@ export interface PokemonSlot {
   posY: number;
   throwAttempts: number;
   spawnTimestamp: BN;
+  /** 0 = common, 1 = uncommon, 2 = rare, 3 = legendary */
+  rarity: number;
 }
 
 /** Parsed PokemonSlots account */
@@ -208,16 +211,27 @@ export async function fetchNftVault(
 
 /**
  * Purchase balls with SolBalls tokens.
+ * Once USD ball prices are set the cost follows the oracle price, so the caller
+ * must pass maxTotalCost (SolBalls atomic units) as a slippage bound.
  */
 export async function purchaseBalls(
   connection: Connection,
   wallet: AnchorWallet,
   ballType: BallType,
-  quantity: number
+  quantity: number,
+  maxTotalCost?: BN
 ): Promise<TransactionSignature> {
   const program = getProgram(connection, wallet);
   const [gameConfigPDA] = getGameConfigPDA();
+
+  const gameConfig = await fetchGameConfig(connection);
+  if (!gameConfig) throw new Error('GameConfig not found');
+  const usdPricing = gameConfig.usdBallPrices.some((price) => !price.isZero());
+  if (usdPricing && !maxTotalCost) {
+    throw new Error('maxTotalCost is required once USD ball prices are set');
+  }
   const [playerInventoryPDA] = getPlayerInventoryPDA(wallet.publicKey);
+  const [spendTrackerPDA] = getSpendTrackerPDA(wallet.publicKey);
 
   // Get the game's SolBalls ATA
   const gameSolballsAccount = await getAssociatedTokenAddress(
@@ -233,13 +247,20 @@ export async function purchaseBalls(
   );
 
   const tx = await program.methods
-    .purchaseBalls(ballType, quantity)
+    .purchaseBalls(
+      ballType,
+      quantity,
+      maxTotalCost ?? gameConfig.ballPrices[ballType].muln(quantity)
+    )
     .accounts({
       player: wallet.publicKey,
       gameConfig: gameConfigPDA,
       playerTokenAccount,
       gameSolballsAccount,
       playerInventory: playerInventoryPDA,
+      spendTracker: spendTrackerPDA,
+      priceFeed: usdPricing ? gameConfig.priceFeed : null,
+      solballsMint: usdPricing ? SOLBALLS_MINT : null,
       tokenProgram: TOKEN_PROGRAM_ID,
     })
     .rpc();
@@ -400,19 +421,30 @@ export async function consumeRandomnessWithRetry(
   // The player's wallet — winner for NFT transfer
   const winnerPubkey = playerPubkey ?? wallet.publicKey;
 
+  // The VRF request is closed on consume; its rent goes back to whoever paid for it.
+  const vrfRequest = await (program.account as any).vrfRequest.fetch(vrfRequestPDA);
+  const requestPayer: PublicKey = vrfRequest.payer;
+
+  // Session throws pass their SessionKey so a voided throw restores its allowance.
+  const session: PublicKey | null =
+    vrfRequest.session && !vrfRequest.session.equals(PublicKey.default) ? vrfRequest.session : null;
+
+  // A catch records a PendingPrize at this PDA; the NFT is delivered by claim_prize.
+  const [pendingPrizePDA] = getPendingPrizePDA(vrfRequestPDA);
+
   // Pass ALL accounts explicitly — do not rely on auto-resolution.
-  // Note: the on-chain struct no longer has optional NFT accounts or rent/associatedToken.
-  // Instead, ALL vault NFTs are passed via remaining_accounts.
   const accounts: Record<string, PublicKey | null> = {
     payer: wallet.publicKey,
     gameConfig: gameConfigPDA,
     pokemonSlots: pokemonSlotsPDA,
     vrfRequest: vrfRequestPDA,
+    requestPayer,
     vrfRandomness,
     nftVault: nftVaultPDA,
     playerInventory: playerInventoryPDA,
     winner: winnerPubkey,
-    tokenProgram: TOKEN_PROGRAM_ID,
+    pendingPrize: pendingPrizePDA,
+    session,
     systemProgram: SystemProgram.programId,
   };
 
@@ -423,70 +455,6 @@ export async function consumeRandomnessWithRetry(
     winner: winnerPubkey.toBase58(),
   });
 
-  // ---- Fetch vault state and build remaining_accounts for NFT transfer ----
-  console.log('[programClient] fetching NftVault for remaining_accounts...');
-  const vault = await fetchNftVault(connection);
-  const remainingAccounts: { pubkey: PublicKey; isWritable: boolean; isSigner: boolean }[] = [];
-
-  if (vault && vault.count > 0) {
-    const activeMints = vault.mints.slice(0, vault.count).filter(
-      (m: PublicKey) => !m.equals(PublicKey.default)
-    );
-
-    console.log(`[programClient] vault has ${activeMints.length} NFTs, building remaining_accounts...`);
-
-    // Derive ATAs for each vault NFT and build remaining_accounts groups of 3
-    const ataCreationIxs: any[] = [];
-
-    for (const mint of activeMints) {
-      const vaultAta = await getAssociatedTokenAddress(mint, nftVaultPDA, true); // allowOwnerOffCurve for PDA
-      const playerAta = await getAssociatedTokenAddress(mint, winnerPubkey);
-
-      // Check if player ATA exists; if not, we need to create it
-      const playerAtaInfo = await connection.getAccountInfo(playerAta);
-      if (!playerAtaInfo) {
-        ataCreationIxs.push(
-          createAssociatedTokenAccountInstruction(
-            wallet.publicKey,  // payer
-            playerAta,         // ATA to create
-            winnerPubkey,      // owner
-            mint               // mint
-          )
-        );
-      }
-
-      // remaining_accounts: [mint (read-only), vault_ata (writable), player_ata (writable)]
-      remainingAccounts.push(
-        { pubkey: mint, isWritable: false, isSigner: false },
-        { pubkey: vaultAta, isWritable: true, isSigner: false },
-        { pubkey: playerAta, isWritable: true, isSigner: false },
-      );
-    }
-
-    // Create any missing player ATAs BEFORE the consume_randomness tx
-    if (ataCreationIxs.length > 0) {
-      console.log(`[programClient] creating ${ataCreationIxs.length} missing player ATA(s)...`);
-      const ataTx = new Transaction();
-      for (const ix of ataCreationIxs) {
-        ataTx.add(ix);
-      }
-      const { blockhash: ataBlockhash } = await connection.getLatestBlockhash('confirmed');
-      ataTx.recentBlockhash = ataBlockhash;
-      ataTx.feePayer = wallet.publicKey;
-      const signedAtaTx = await wallet.signTransaction(ataTx);
-      const ataRawTx = signedAtaTx.serialize();
-      const ataTxSig = await connection.sendRawTransaction(ataRawTx, {
-        skipPreflight: false,
-        preflightCommitment: 'confirmed',
-      });
-      console.log(`[programClient] ATA creation tx: ${ataTxSig}`);
-      await connection.confirmTransaction(ataTxSig, 'confirmed');
-      console.log('[programClient] ATA creation confirmed');
-    }
-  } else {
-    console.log('[programClient] vault empty or not found, no remaining_accounts needed');
-  }
-
   // Wait for ORAO VRF fulfillment — sub-second, but give it 2s margin
   console.log('[programClient] waiting 2s for ORAO VRF fulfillment...');
   await new Promise((r) => setTimeout(r, 2_000));
@@ -496,58 +464,16 @@ export async function consumeRandomnessWithRetry(
   const consumeIx = await program.methods
     .consumeRandomness()
     .accounts(accounts)
-    .remainingAccounts(remainingAccounts)
     .instruction();
 
-  // ---- Choose legacy vs versioned transaction based on vault size ----
-  // Legacy tx fits ~7 NFTs (1232 byte limit). Above that, use VersionedTransaction with ALT.
-  const MAX_LEGACY_NFTS = 7;
-  const vaultNftCount = vault ? vault.count : 0;
-  let rawTx: Buffer;
-  let blockhash: string;
-  let lastValidBlockHeight: number;
-
-  if (vaultNftCount <= MAX_LEGACY_NFTS) {
-    // ---- Legacy Transaction path ----
-    console.log(`[programClient] using legacy transaction (${vaultNftCount} vault NFTs)`);
-    const tx = new Transaction().add(consumeIx);
-    ({ blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash('confirmed'));
-    tx.recentBlockhash = blockhash;
-    tx.feePayer = wallet.publicKey;
-
-    console.log('[programClient] requesting wallet signature (one popup)...');
-    const signedTx = await wallet.signTransaction(tx);
-    rawTx = signedTx.serialize() as Buffer;
-  } else {
-    // ---- Versioned Transaction (v0) with Address Lookup Table ----
-    console.log(`[programClient] using versioned transaction with ALT (${vaultNftCount} vault NFTs)`);
-    const altAddress = import.meta.env.VITE_VAULT_ALT_ADDRESS;
-    if (!altAddress) {
-      throw new Error(
-        `Vault has ${vaultNftCount} NFTs (> ${MAX_LEGACY_NFTS}), but VITE_VAULT_ALT_ADDRESS is not set. ` +
-        'An Address Lookup Table is required for large vaults.'
-      );
-    }
+  const tx = new Transaction().add(consumeIx);
+  const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash('confirmed');
+  tx.recentBlockhash = blockhash;
+  tx.feePayer = wallet.publicKey;
 
-    const altPubkey = new PublicKey(altAddress);
-    const altAccount = await connection.getAddressLookupTable(altPubkey);
-    if (!altAccount.value) {
-      throw new Error(`Address Lookup Table ${altAddress} not found on-chain`);
-    }
-
-    ({ blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash('confirmed'));
-    const messageV0 = new TransactionMessage({
-      payerKey: wallet.publicKey,
-      recentBlockhash: blockhash,
-      instructions: [consumeIx],
-    }).compileToV0Message([altAccount.value]);
-
-    const versionedTx = new VersionedTransaction(messageV0);
-
-    console.log('[programClient] requesting wallet signature (one popup, versioned tx)...');
-    const signedVersionedTx = await wallet.signTransaction(versionedTx);
-    rawTx = Buffer.from(signedVersionedTx.serialize());
-  }
+  console.log('[programClient] requesting wallet signature (one popup)...');
+  const signedTx = await wallet.signTransaction(tx);
+  const rawTx = signedTx.serialize() as Buffer;
 
   console.log('[programClient] transaction signed, sending...');
 
@@ -641,6 +567,51 @@ export async function consumeRandomnessWithRetry(
   throw new Error(`VRF fulfillment timeout after ${attempt} attempts: ${lastError?.message ?? 'unknown'}`);
 }
 
+/**
+ * Deliver a caught NFT from the vault to the winner.
+ * Returns null if the throw did not record a prize (miss or empty vault).
+ */
+export async function claimPrize(
+  connection: Connection,
+  wallet: AnchorWallet,
+  vrfRequestPDA: PublicKey
+): Promise<TransactionSignature | null> {
+  const program = getProgram(connection, wallet);
+  const [gameConfigPDA] = getGameConfigPDA();
+  const [nftVaultPDA] = getNftVaultPDA();
+  const [pendingPrizePDA] = getPendingPrizePDA(vrfRequestPDA);
+
+  const prize = await (program.account as any).pendingPrize.fetchNullable(pendingPrizePDA);
+  if (!prize) {
+    console.log('[programClient] no pending prize for', vrfRequestPDA.toBase58());
+    return null;
+  }
+
+  const vaultNftAccount = await getAssociatedTokenAddress(prize.nftMint, nftVaultPDA, true);
+  const winnerNftAccount = await getAssociatedTokenAddress(prize.nftMint, prize.winner);
+
+  const tx = await program.methods
+    .claimPrize()
+    .accounts({
+      payer: wallet.publicKey,
+      gameConfig: gameConfigPDA,
+      nftVault: nftVaultPDA,
+      pendingPrize: pendingPrizePDA,
+      rentPayer: prize.rentPayer,
+      winner: prize.winner,
+      nftMint: prize.nftMint,
+      vaultNftAccount,
+      winnerNftAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  console.log('[programClient] claimPrize tx:', tx);
+  return tx;
+}
+
 // ============================================================
 // EVENT TYPES
 // ============================================================
@@ -665,6 +636,7 @@ export interface CaughtPokemonEvent {
   pokemonId: BN;
   slotIndex: number;
   nftMint: PublicKey;
+  rarity: number;
 }
 
 export interface FailedCatchEvent {
@@ -679,6 +651,7 @@ export interface PokemonSpawnedEvent {
   slotIndex: number;
   posX: number;
   posY: number;
+  rarity: number;
 }
 
 export interface PokemonDespawnedEvent {
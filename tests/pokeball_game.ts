@@ -7,6 +7,8 @@ import {
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
   LAMPORTS_PER_SOL,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
@@ -15,10 +17,23 @@ import {
   mintTo,
   getAssociatedTokenAddress,
   createAssociatedTokenAccount,
+  getOrCreateAssociatedTokenAccount,
   getAccount,
+  getMint,
 } from "@solana/spl-token";
 import { assert, expect } from "chai";
 
+// Mock SolBalls/USD feeds (Pyth layout, $0.05, expo -8) from tests/fixtures.
+// Only present when running against a local validator.
+const MOCK_FEED_FRESH = new PublicKey("ChSh1DtCdaeCiNAGdsnwtuY9LFt5XEULjQn6qZWdVR6K");
+const MOCK_FEED_STALE = new PublicKey("9hXUu1nAWEh9wQuZ5yy1haGjigFgYtFhkUxrEhvBp5Wd");
+const MOCK_FEED_WIDE = new PublicKey("2o5YoPu3mEVdBNP66ExhVd5NSeNHwiMBiXgKM5S1R3nP");
+const MOCK_FEED_UNOWNED = new PublicKey("GPqXSrR98s9tTBQYuughQWKE5WGp119Jajjhcazs7bxq");
+
+// Unclaimed PendingPrize from tests/fixtures. Its NFT sits in the vault's ATA
+// and its winner has no keypair, so claims must be cranked by someone else.
+const FIXTURE_PENDING_PRIZE = new PublicKey("8XawLh12M6vs47sqKQHAb5GVrB9YCmJz57zsPpnftgYm");
+
 // ORAO VRF program constants
 const ORAO_VRF_PROGRAM_ID = new PublicKey(
   "VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y"
@@ -33,6 +48,14 @@ const PLAYER_INV_SEED = Buffer.from("player_inv");
 const NFT_VAULT_SEED = Buffer.from("nft_vault");
 const TREASURY_SEED = Buffer.from("treasury");
 const VRF_REQ_SEED = Buffer.from("vrf_req");
+const PENDING_CONFIG_SEED = Buffer.from("pending_config");
+const PENDING_PRIZE_SEED = Buffer.from("pending_prize");
+const SOL_TREASURY_SEED = Buffer.from("sol_treasury");
+const PRIZE_POOL_SEED = Buffer.from("prize_pool");
+const RESERVE_SEED = Buffer.from("reserve");
+const SESSION_SEED = Buffer.from("session");
+const SPEND_TRACKER_SEED = Buffer.from("spend_tracker");
+const BALL_MINT_SEED = Buffer.from("ball_mint");
 
 // Default ball prices (6-decimal SolBalls atomic units)
 const DEFAULT_BALL_PRICES = [
@@ -43,8 +66,54 @@ const DEFAULT_BALL_PRICES = [
 ];
 
 // Default catch rates (percent)
+// USDC prices (6 decimals): $1.00, $5.00, $10.00, $49.90
+const USDC_BALL_PRICES = [
+  new BN(1_000_000),
+  new BN(5_000_000),
+  new BN(10_000_000),
+  new BN(49_900_000),
+];
+const NO_USD_BALL_PRICES = [new BN(0), new BN(0), new BN(0), new BN(0)];
+// SOL prices in lamports: 0.01, 0.05, 0.1, 0.5 SOL
+const SOL_BALL_PRICES = [
+  new BN(10_000_000),
+  new BN(50_000_000),
+  new BN(100_000_000),
+  new BN(500_000_000),
+];
+// USD prices (micro-units), paid in SolBalls at the oracle price
+const USD_BALL_PRICES = [
+  new BN(1_000_000),
+  new BN(5_000_000),
+  new BN(10_000_000),
+  new BN(49_900_000),
+];
+// max_total_cost for purchases at the fixed ball_prices, where the cost can't move
+const ANY_COST = new BN("18446744073709551615");
 const DEFAULT_CATCH_RATES = [2, 20, 50, 99];
 
+// Pokemon rarities (must match program)
+const RARITY_COMMON = 0;
+const RARITY_LEGENDARY = 3;
+// Default spawn odds and catch-rate multipliers (bps)
+const DEFAULT_RARITY_WEIGHTS = [7_000, 2_000, 900, 100];
+const DEFAULT_RARITY_CATCH_MULTIPLIERS = [10_000, 8_000, 5_000, 2_500];
+
+// Pause flags (must match program)
+const PAUSE_PURCHASES = 1 << 0;
+const PAUSE_THROWS = 1 << 1;
+const PAUSE_SPAWNS = 1 << 2;
+const PAUSE_NFT_AWARDS = 1 << 3;
+const PAUSE_TRANSFERS = 1 << 4;
+const PAUSE_BALL_TOKENS = 1 << 5;
+const PAUSE_CRAFTING = 1 << 6;
+
+// Roles (must match program)
+const ROLE_CONFIG_ADMIN = 0;
+const ROLE_SPAWNER = 1;
+const ROLE_VAULT_MANAGER = 2;
+const ROLE_TREASURER = 3;
+
 describe("pokeball_game", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -67,6 +136,9 @@ describe("pokeball_game", () => {
   let nftVaultBump: number;
   let treasuryConfigPda: PublicKey;
   let treasuryConfigBump: number;
+  let solTreasuryPda: PublicKey;
+  let prizePoolPda: PublicKey;
+  let reservePda: PublicKey;
   let gameSolballsAta: PublicKey;
 
   // Player accounts
@@ -78,6 +150,26 @@ describe("pokeball_game", () => {
   let nftMint2: PublicKey;
   let nftMint3: PublicKey;
 
+  /** Current GameConfig values in the shape queue_config_change expects. */
+  const currentValues = async () => {
+    const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+    return {
+      ballPrices: gameConfig.ballPrices,
+      catchRates: gameConfig.catchRates,
+      maxActivePokemon: gameConfig.maxActivePokemon,
+      usdcBallPrices: gameConfig.usdcBallPrices,
+      solBallPrices: gameConfig.solBallPrices,
+      usdBallPrices: gameConfig.usdBallPrices,
+      rarityWeights: gameConfig.rarityWeights,
+      rarityCatchMultipliers: gameConfig.rarityCatchMultipliers,
+      priceFeed: gameConfig.priceFeed,
+      maxPriceAge: gameConfig.maxPriceAge,
+      maxPriceConfBps: gameConfig.maxPriceConfBps,
+      refundWindow: gameConfig.refundWindow,
+      refundHaircutBps: gameConfig.refundHaircutBps,
+    };
+  };
+
   before(async () => {
     // Generate keypairs
     treasuryKeypair = Keypair.generate();
@@ -125,6 +217,18 @@ describe("pokeball_game", () => {
       [TREASURY_SEED],
       program.programId
     );
+    [solTreasuryPda] = PublicKey.findProgramAddressSync(
+      [SOL_TREASURY_SEED],
+      program.programId
+    );
+    [prizePoolPda] = PublicKey.findProgramAddressSync(
+      [PRIZE_POOL_SEED],
+      program.programId
+    );
+    [reservePda] = PublicKey.findProgramAddressSync(
+      [RESERVE_SEED],
+      program.programId
+    );
 
     // Derive game's SolBalls ATA (owned by gameConfigPda)
     gameSolballsAta = await getAssociatedTokenAddress(
@@ -219,6 +323,7 @@ describe("pokeball_game", () => {
           pokemonSlots: pokemonSlotsPda,
           nftVault: nftVaultPda,
           treasuryConfig: treasuryConfigPda,
+          solTreasury: solTreasuryPda,
           solballsMint: solballsMint,
           gameSolballsAccount: gameSolballsAta,
           systemProgram: SystemProgram.programId,
@@ -232,7 +337,6 @@ describe("pokeball_game", () => {
       const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
       assert.isTrue(gameConfig.isInitialized);
       assert.ok(gameConfig.authority.equals(authority.publicKey));
-      assert.ok(gameConfig.treasury.equals(treasuryKeypair.publicKey));
       assert.ok(gameConfig.solballsMint.equals(solballsMint));
       assert.ok(gameConfig.usdcMint.equals(usdcMint));
       assert.equal(gameConfig.maxActivePokemon, 20);
@@ -269,6 +373,12 @@ describe("pokeball_game", () => {
       const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
       assert.ok(treasuryConfig.treasuryWallet.equals(treasuryKeypair.publicKey));
       assert.equal(treasuryConfig.totalWithdrawn.toNumber(), 0);
+
+      // SOL treasury is pre-funded so small first purchases don't fail rent checks
+      assert.equal(
+        await provider.connection.getBalance(solTreasuryPda),
+        await provider.connection.getMinimumBalanceForRentExemption(0)
+      );
     });
 
     it("fails to initialize twice", async () => {
@@ -287,6 +397,7 @@ describe("pokeball_game", () => {
             pokemonSlots: pokemonSlotsPda,
             nftVault: nftVaultPda,
             treasuryConfig: treasuryConfigPda,
+            solTreasury: solTreasuryPda,
             solballsMint: solballsMint,
             gameSolballsAccount: gameSolballsAta,
             systemProgram: SystemProgram.programId,
@@ -313,7 +424,7 @@ describe("pokeball_game", () => {
       const ballType = 0; // Poke Ball
 
       await program.methods
-        .purchaseBalls(ballType, quantity)
+        .purchaseBalls(ballType, quantity, ANY_COST)
         .accounts({
           player: playerKeypair.publicKey,
           gameConfig: gameConfigPda,
@@ -350,7 +461,7 @@ describe("pokeball_game", () => {
       const ballType = 1; // Great Ball
 
       await program.methods
-        .purchaseBalls(ballType, quantity)
+        .purchaseBalls(ballType, quantity, ANY_COST)
         .accounts({
           player: playerKeypair.publicKey,
           gameConfig: gameConfigPda,
@@ -372,7 +483,7 @@ describe("pokeball_game", () => {
     it("fails with invalid ball type", async () => {
       try {
         await program.methods
-          .purchaseBalls(4, 1) // Ball type 4 doesn't exist
+          .purchaseBalls(4, 1, ANY_COST) // Ball type 4 doesn't exist
           .accounts({
             player: playerKeypair.publicKey,
             gameConfig: gameConfigPda,
@@ -393,7 +504,7 @@ describe("pokeball_game", () => {
     it("fails with zero quantity", async () => {
       try {
         await program.methods
-          .purchaseBalls(0, 0)
+          .purchaseBalls(0, 0, ANY_COST)
           .accounts({
             player: playerKeypair.publicKey,
             gameConfig: gameConfigPda,
@@ -415,7 +526,7 @@ describe("pokeball_game", () => {
       try {
         // Try to buy an absurd amount
         await program.methods
-          .purchaseBalls(3, 1000000) // 1M Master Balls = way more than player has
+          .purchaseBalls(3, 1000000, ANY_COST) // 1M Master Balls = way more than player has
           .accounts({
             player: playerKeypair.publicKey,
             gameConfig: gameConfigPda,
@@ -432,6 +543,104 @@ describe("pokeball_game", () => {
         assert.ok(err);
       }
     });
+
+    it("player purchases a mixed bundle with one transfer", async () => {
+      const quantities = [2, 1, 0, 0];
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      const configBefore = await program.account.gameConfig.fetch(gameConfigPda);
+      const gameBefore = Number((await getAccount(provider.connection, gameSolballsAta)).amount);
+
+      const tx = await program.methods
+        .purchaseBallBundle(quantities, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      const expectedCost = quantities.reduce(
+        (sum, quantity, i) => sum + DEFAULT_BALL_PRICES[i].toNumber() * quantity,
+        0
+      );
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      for (let i = 0; i < 4; i++) {
+        assert.equal(after.balls[i], before.balls[i] + quantities[i]);
+      }
+      assert.equal(
+        after.totalPurchased.toNumber(),
+        before.totalPurchased.toNumber() + 3
+      );
+
+      const configAfter = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(
+        configAfter.totalRevenue.toNumber() - configBefore.totalRevenue.toNumber(),
+        expectedCost
+      );
+      const gameAfter = Number((await getAccount(provider.connection, gameSolballsAta)).amount);
+      assert.equal(gameAfter - gameBefore, expectedCost);
+
+      // One token transfer for the whole bundle
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const transfers = txInfo.meta.innerInstructions
+        .flatMap((ix) => ix.instructions)
+        .filter((ix) =>
+          txInfo.transaction.message.staticAccountKeys[ix.programIdIndex].equals(TOKEN_PROGRAM_ID)
+        );
+      assert.equal(transfers.length, 1);
+    });
+
+    it("bundle fails when the combined total exceeds the cap", async () => {
+      try {
+        // 1,001 Master Balls alone is over 49,900 SolBalls
+        await program.methods
+          .purchaseBallBundle([0, 0, 0, 1001], ANY_COST)
+          .accounts({
+            player: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            playerTokenAccount: playerSolballsAta,
+            gameSolballsAccount: gameSolballsAta,
+            playerInventory: playerInventoryPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with PurchaseExceedsMax");
+      } catch (err) {
+        assert.ok(err.toString().includes("PurchaseExceedsMax"));
+      }
+    });
+
+    it("bundle fails when every quantity is zero", async () => {
+      try {
+        await program.methods
+          .purchaseBallBundle([0, 0, 0, 0], ANY_COST)
+          .accounts({
+            player: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            playerTokenAccount: playerSolballsAta,
+            gameSolballsAccount: gameSolballsAta,
+            playerInventory: playerInventoryPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with ZeroQuantity");
+      } catch (err) {
+        assert.ok(err.toString().includes("ZeroQuantity"));
+      }
+    });
   });
 
   // ============================================================
@@ -441,7 +650,7 @@ describe("pokeball_game", () => {
   describe("force_spawn_pokemon", () => {
     it("authority force-spawns a Pokemon", async () => {
       await program.methods
-        .forceSpawnPokemon(0, 500, 500)
+        .forceSpawnPokemon(0, 500, 500, RARITY_COMMON)
         .accounts({
           authority: authority.publicKey,
           gameConfig: gameConfigPda,
@@ -464,7 +673,7 @@ describe("pokeball_game", () => {
 
     it("spawns multiple Pokemon in different slots", async () => {
       await program.methods
-        .forceSpawnPokemon(5, 100, 200)
+        .forceSpawnPokemon(5, 100, 200, RARITY_COMMON)
         .accounts({
           authority: authority.publicKey,
           gameConfig: gameConfigPda,
@@ -473,7 +682,7 @@ describe("pokeball_game", () => {
         .rpc();
 
       await program.methods
-        .forceSpawnPokemon(10, 800, 900)
+        .forceSpawnPokemon(10, 800, 900, RARITY_COMMON)
         .accounts({
           authority: authority.publicKey,
           gameConfig: gameConfigPda,
@@ -492,7 +701,7 @@ describe("pokeball_game", () => {
     it("fails on occupied slot", async () => {
       try {
         await program.methods
-          .forceSpawnPokemon(0, 100, 100) // Slot 0 already occupied
+          .forceSpawnPokemon(0, 100, 100, RARITY_COMMON) // Slot 0 already occupied
           .accounts({
             authority: authority.publicKey,
             gameConfig: gameConfigPda,
@@ -508,7 +717,7 @@ describe("pokeball_game", () => {
     it("fails on invalid slot index", async () => {
       try {
         await program.methods
-          .forceSpawnPokemon(20, 100, 100) // Max is 19
+          .forceSpawnPokemon(20, 100, 100, RARITY_COMMON) // Max is 19
           .accounts({
             authority: authority.publicKey,
             gameConfig: gameConfigPda,
@@ -524,7 +733,7 @@ describe("pokeball_game", () => {
     it("fails on invalid coordinates", async () => {
       try {
         await program.methods
-          .forceSpawnPokemon(1, 1000, 500) // Max coordinate is 999
+          .forceSpawnPokemon(1, 1000, 500, RARITY_COMMON) // Max coordinate is 999
           .accounts({
             authority: authority.publicKey,
             gameConfig: gameConfigPda,
@@ -537,10 +746,53 @@ describe("pokeball_game", () => {
       }
     });
 
+    it("stores the given rarity on the slot", async () => {
+      await program.methods
+        .forceSpawnPokemon(1, 400, 400, RARITY_LEGENDARY)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pokemonSlots: pokemonSlotsPda,
+        })
+        .rpc();
+
+      let pokemonSlots = await program.account.pokemonSlots.fetch(pokemonSlotsPda);
+      assert.equal(pokemonSlots.slots[1].rarity, RARITY_LEGENDARY);
+      assert.equal(pokemonSlots.slots[0].rarity, RARITY_COMMON);
+
+      // Free the slot again for later tests
+      await program.methods
+        .despawnPokemon(1)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pokemonSlots: pokemonSlotsPda,
+        })
+        .rpc();
+      pokemonSlots = await program.account.pokemonSlots.fetch(pokemonSlotsPda);
+      assert.isFalse(pokemonSlots.slots[1].isActive);
+    });
+
+    it("fails on invalid rarity", async () => {
+      try {
+        await program.methods
+          .forceSpawnPokemon(1, 100, 100, RARITY_LEGENDARY + 1)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+          })
+          .rpc();
+        assert.fail("Should have failed on invalid rarity");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRarity"));
+      }
+    });
+
     it("fails for non-authority signer", async () => {
       try {
         await program.methods
-          .forceSpawnPokemon(1, 100, 100)
+          .forceSpawnPokemon(1, 100, 100, RARITY_COMMON)
           .accounts({
             authority: playerKeypair.publicKey, // Not the authority
             gameConfig: gameConfigPda,
@@ -814,58 +1066,133 @@ describe("pokeball_game", () => {
   // ============================================================
 
   describe("admin configuration", () => {
-    it("sets ball price", async () => {
-      const newPrice = new BN(2_000_000); // $2 for Poke Ball
+    const [pendingConfigPda] = PublicKey.findProgramAddressSync(
+      [PENDING_CONFIG_SEED],
+      program.programId
+    );
+
+    const queueAndExecute = async (values: any) => {
+      await program.methods
+        .queueConfigChange(values)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .executeConfigChange()
+        .accounts({
+          executor: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+    };
 
+    const setDelay = async (delay: number) => {
       await program.methods
-        .setBallPrice(0, newPrice)
+        .setConfigChangeDelay(new BN(delay))
         .accounts({
           authority: authority.publicKey,
           gameConfig: gameConfigPda,
         })
         .rpc();
+    };
+
+    it("sets config change delay to zero for tests", async () => {
+      // Tests run against a `localnet` build, which has no minimum delay
+      let gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.configChangeDelay.toNumber(), 0);
+
+      await setDelay(0);
+
+      gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.configChangeDelay.toNumber(), 0);
+    });
+
+    it("sets ball price", async () => {
+      const values = await currentValues();
+      values.ballPrices[0] = new BN(2_000_000); // $2 for Poke Ball
+      await queueAndExecute(values);
 
       const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
       assert.equal(gameConfig.ballPrices[0].toNumber(), 2_000_000);
+
+      // Pending change is closed after execution
+      const pending = await provider.connection.getAccountInfo(pendingConfigPda);
+      assert.isNull(pending);
     });
 
     it("fails with zero ball price", async () => {
+      const values = await currentValues();
+      values.ballPrices[0] = new BN(0);
       try {
-        await program.methods
-          .setBallPrice(0, new BN(0))
-          .accounts({
-            authority: authority.publicKey,
-            gameConfig: gameConfigPda,
-          })
-          .rpc();
+        await queueAndExecute(values);
         assert.fail("Should have failed with zero price");
       } catch (err) {
         assert.ok(err.toString().includes("ZeroBallPrice"));
       }
     });
 
+    it("sets USDC ball prices", async () => {
+      const values = await currentValues();
+      values.usdcBallPrices = USDC_BALL_PRICES;
+      await queueAndExecute(values);
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      for (let i = 0; i < 4; i++) {
+        assert.equal(
+          gameConfig.usdcBallPrices[i].toNumber(),
+          USDC_BALL_PRICES[i].toNumber()
+        );
+      }
+    });
+
+    it("sets SOL ball prices", async () => {
+      const values = await currentValues();
+      values.solBallPrices = SOL_BALL_PRICES;
+      await queueAndExecute(values);
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      for (let i = 0; i < 4; i++) {
+        assert.equal(
+          gameConfig.solBallPrices[i].toNumber(),
+          SOL_BALL_PRICES[i].toNumber()
+        );
+      }
+    });
+
+    it("sets USD ball prices", async () => {
+      const values = await currentValues();
+      values.usdBallPrices = USD_BALL_PRICES;
+      await queueAndExecute(values);
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.usdBallPrices[0].toNumber(), USD_BALL_PRICES[0].toNumber());
+
+      // Clear them again so later purchases stay on the fixed ball_prices
+      values.usdBallPrices = NO_USD_BALL_PRICES;
+      await queueAndExecute(values);
+    });
+
     it("sets catch rate", async () => {
-      await program.methods
-        .setCatchRate(0, 5) // 5% for Poke Ball
-        .accounts({
-          authority: authority.publicKey,
-          gameConfig: gameConfigPda,
-        })
-        .rpc();
+      const values = await currentValues();
+      values.catchRates[0] = 5; // 5% for Poke Ball
+      await queueAndExecute(values);
 
       const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
       assert.equal(gameConfig.catchRates[0], 5);
     });
 
     it("fails with invalid catch rate (> 100)", async () => {
+      const values = await currentValues();
+      values.catchRates[0] = 101;
       try {
-        await program.methods
-          .setCatchRate(0, 101)
-          .accounts({
-            authority: authority.publicKey,
-            gameConfig: gameConfigPda,
-          })
-          .rpc();
+        await queueAndExecute(values);
         assert.fail("Should have failed with invalid catch rate");
       } catch (err) {
         assert.ok(err.toString().includes("InvalidCatchRate"));
@@ -873,36 +1200,23 @@ describe("pokeball_game", () => {
     });
 
     it("sets max active Pokemon", async () => {
-      await program.methods
-        .setMaxActivePokemon(10)
-        .accounts({
-          authority: authority.publicKey,
-          gameConfig: gameConfigPda,
-        })
-        .rpc();
+      const values = await currentValues();
+      values.maxActivePokemon = 10;
+      await queueAndExecute(values);
 
       const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
       assert.equal(gameConfig.maxActivePokemon, 10);
 
       // Reset back to 20 for remaining tests
-      await program.methods
-        .setMaxActivePokemon(20)
-        .accounts({
-          authority: authority.publicKey,
-          gameConfig: gameConfigPda,
-        })
-        .rpc();
+      values.maxActivePokemon = 20;
+      await queueAndExecute(values);
     });
 
     it("fails with max active Pokemon of 0", async () => {
+      const values = await currentValues();
+      values.maxActivePokemon = 0;
       try {
-        await program.methods
-          .setMaxActivePokemon(0)
-          .accounts({
-            authority: authority.publicKey,
-            gameConfig: gameConfigPda,
-          })
-          .rpc();
+        await queueAndExecute(values);
         assert.fail("Should have failed with 0 max");
       } catch (err) {
         assert.ok(err.toString().includes("InvalidMaxActivePokemon"));
@@ -910,36 +1224,69 @@ describe("pokeball_game", () => {
     });
 
     it("fails with max active Pokemon > 20", async () => {
+      const values = await currentValues();
+      values.maxActivePokemon = 21;
       try {
-        await program.methods
-          .setMaxActivePokemon(21)
-          .accounts({
-            authority: authority.publicKey,
-            gameConfig: gameConfigPda,
-          })
-          .rpc();
+        await queueAndExecute(values);
         assert.fail("Should have failed with max > 20");
       } catch (err) {
         assert.ok(err.toString().includes("InvalidMaxActivePokemon"));
       }
     });
 
-    it("resets ball price back to default", async () => {
-      await program.methods
-        .setBallPrice(0, DEFAULT_BALL_PRICES[0])
-        .accounts({
-          authority: authority.publicKey,
-          gameConfig: gameConfigPda,
-        })
-        .rpc();
+    it("initializes default rarity weights and catch multipliers", async () => {
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.deepEqual(gameConfig.rarityWeights, DEFAULT_RARITY_WEIGHTS);
+      assert.deepEqual(gameConfig.rarityCatchMultipliers, DEFAULT_RARITY_CATCH_MULTIPLIERS);
+    });
 
-      await program.methods
-        .setCatchRate(0, DEFAULT_CATCH_RATES[0])
-        .accounts({
-          authority: authority.publicKey,
-          gameConfig: gameConfigPda,
-        })
-        .rpc();
+    it("sets rarity weights and catch multipliers", async () => {
+      const values = await currentValues();
+      values.rarityWeights = [6_000, 2_500, 1_200, 300];
+      values.rarityCatchMultipliers = [12_000, 8_000, 4_000, 1_000];
+      await queueAndExecute(values);
+
+      let gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.deepEqual(gameConfig.rarityWeights, [6_000, 2_500, 1_200, 300]);
+      assert.deepEqual(gameConfig.rarityCatchMultipliers, [12_000, 8_000, 4_000, 1_000]);
+
+      // Restore defaults for remaining tests
+      values.rarityWeights = DEFAULT_RARITY_WEIGHTS;
+      values.rarityCatchMultipliers = DEFAULT_RARITY_CATCH_MULTIPLIERS;
+      await queueAndExecute(values);
+      gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.deepEqual(gameConfig.rarityWeights, DEFAULT_RARITY_WEIGHTS);
+    });
+
+    it("fails when rarity weights do not sum to 10,000", async () => {
+      const values = await currentValues();
+      values.rarityWeights = [7_000, 2_000, 900, 99];
+      try {
+        await queueAndExecute(values);
+        assert.fail("Should have failed with invalid weights");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRarityWeights"));
+      }
+    });
+
+    it("fails with a zero or oversized rarity multiplier", async () => {
+      for (const multiplier of [0, 20_001]) {
+        const values = await currentValues();
+        values.rarityCatchMultipliers = [10_000, 8_000, 5_000, multiplier];
+        try {
+          await queueAndExecute(values);
+          assert.fail("Should have failed with invalid multiplier");
+        } catch (err) {
+          assert.ok(err.toString().includes("InvalidRarityMultiplier"));
+        }
+      }
+    });
+
+    it("resets ball price back to default", async () => {
+      const values = await currentValues();
+      values.ballPrices[0] = DEFAULT_BALL_PRICES[0];
+      values.catchRates[0] = DEFAULT_CATCH_RATES[0];
+      await queueAndExecute(values);
 
       const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
       assert.equal(gameConfig.ballPrices[0].toNumber(), DEFAULT_BALL_PRICES[0].toNumber());
@@ -948,212 +1295,3128 @@ describe("pokeball_game", () => {
   });
 
   // ============================================================
-  // WITHDRAW REVENUE
+  // USDC PURCHASES
   // ============================================================
 
-  describe("withdraw_revenue", () => {
-    it("authority withdraws SolBalls revenue", async () => {
-      // First, get the current game SolBalls balance
-      const gameTokenAccount = await getAccount(provider.connection, gameSolballsAta);
-      const balance = Number(gameTokenAccount.amount);
-      assert.isAbove(balance, 0, "Game should have SolBalls from purchases");
+  describe("purchase_balls_with_usdc", () => {
+    let playerUsdcAta: PublicKey;
+    let gameUsdcAta: PublicKey;
 
-      // Create authority's SolBalls ATA if needed
-      let authoritySolballsAta: PublicKey;
-      try {
-        authoritySolballsAta = await createAssociatedTokenAccount(
-          provider.connection,
-          (authority as any).payer,
-          solballsMint,
-          authority.publicKey
-        );
-      } catch {
-        authoritySolballsAta = await getAssociatedTokenAddress(
-          solballsMint,
-          authority.publicKey
-        );
-      }
+    const usdcAccounts = () => ({
+      player: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      usdcMint,
+      playerUsdcAccount: playerUsdcAta,
+      gameUsdcAccount: gameUsdcAta,
+      playerInventory: playerInventoryPda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      gameUsdcAta = await getAssociatedTokenAddress(usdcMint, gameConfigPda, true);
+      playerUsdcAta = await createAssociatedTokenAccount(
+        provider.connection,
+        (authority as any).payer,
+        usdcMint,
+        playerKeypair.publicKey
+      );
+      // $100 USDC
+      await mintTo(
+        provider.connection,
+        (authority as any).payer,
+        usdcMint,
+        playerUsdcAta,
+        authority.publicKey,
+        100_000_000
+      );
+    });
 
-      const withdrawAmount = new BN(Math.floor(balance / 2)); // Withdraw half
+    it("player purchases Great Balls with USDC", async () => {
+      const quantity = 2;
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      const configBefore = await program.account.gameConfig.fetch(gameConfigPda);
 
       await program.methods
-        .withdrawRevenue(withdrawAmount)
-        .accounts({
-          authority: authority.publicKey,
-          gameConfig: gameConfigPda,
-          treasuryConfig: treasuryConfigPda,
-          gameSolballsAccount: gameSolballsAta,
-          authoritySolballsAccount: authoritySolballsAta,
-          tokenProgram: TOKEN_PROGRAM_ID,
+        .purchaseBallsWithUsdc(1, quantity)
+        .accounts(usdcAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const expectedCost = USDC_BALL_PRICES[1].toNumber() * quantity;
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[1], before.balls[1] + quantity);
+      assert.equal(
+        after.totalPurchased.toNumber(),
+        before.totalPurchased.toNumber() + quantity
+      );
+
+      const configAfter = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(configAfter.totalUsdcRevenue.toNumber(), expectedCost);
+      // SolBalls revenue is tracked separately
+      assert.equal(
+        configAfter.totalRevenue.toNumber(),
+        configBefore.totalRevenue.toNumber()
+      );
+
+      const gameUsdc = await getAccount(provider.connection, gameUsdcAta);
+      assert.equal(Number(gameUsdc.amount), expectedCost);
+    });
+
+    it("fails above the USDC purchase cap", async () => {
+      try {
+        // 2 Master Balls = $99.80 > $49.90
+        await program.methods
+          .purchaseBallsWithUsdc(3, 2)
+          .accounts(usdcAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with PurchaseExceedsMax");
+      } catch (err) {
+        assert.ok(err.toString().includes("PurchaseExceedsMax"));
+      }
+    });
+
+    it("fails when paying with a non-USDC token account", async () => {
+      try {
+        await program.methods
+          .purchaseBallsWithUsdc(0, 1)
+          .accounts({ ...usdcAccounts(), playerUsdcAccount: playerSolballsAta })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with a mint mismatch");
+      } catch (err) {
+        assert.ok(err.toString().includes("ConstraintRaw"));
+      }
+    });
+
+    it("treasurer withdraws USDC revenue to the treasury wallet", async () => {
+      const treasuryUsdcAta = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        (authority as any).payer,
+        usdcMint,
+        treasuryKeypair.publicKey
+      );
+      const amount = USDC_BALL_PRICES[1].toNumber();
+
+      await program.methods
+        .withdrawUsdcRevenue(new BN(amount))
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          treasuryConfig: treasuryConfigPda,
+          gameUsdcAccount: gameUsdcAta,
+          treasuryUsdcAccount: treasuryUsdcAta.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.equal(treasuryConfig.totalUsdcWithdrawn.toNumber(), amount);
+
+      const received = await getAccount(provider.connection, treasuryUsdcAta.address);
+      assert.equal(Number(received.amount), amount);
+    });
+  });
+
+  // ============================================================
+  // SOL PURCHASES
+  // ============================================================
+
+  describe("purchase_balls_with_sol", () => {
+    const solAccounts = () => ({
+      player: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      solTreasury: solTreasuryPda,
+      playerInventory: playerInventoryPda,
+      systemProgram: SystemProgram.programId,
+    });
+
+    it("player purchases Ultra Balls with SOL", async () => {
+      const quantity = 2;
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      const treasuryBefore = await provider.connection.getBalance(solTreasuryPda);
+
+      await program.methods
+        .purchaseBallsWithSol(2, quantity)
+        .accounts(solAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const expectedCost = SOL_BALL_PRICES[2].toNumber() * quantity;
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[2], before.balls[2] + quantity);
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.totalSolRevenue.toNumber(), expectedCost);
+
+      const treasuryAfter = await provider.connection.getBalance(solTreasuryPda);
+      assert.equal(treasuryAfter - treasuryBefore, expectedCost);
+    });
+
+    it("fails with zero quantity", async () => {
+      try {
+        await program.methods
+          .purchaseBallsWithSol(0, 0)
+          .accounts(solAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with zero quantity");
+      } catch (err) {
+        assert.ok(err.toString().includes("ZeroQuantity"));
+      }
+    });
+
+    it("fails when exceeding max SOL purchase amount", async () => {
+      // 2 Master Balls at 0.5 SOL = 1 SOL > 0.5 SOL max
+      try {
+        await program.methods
+          .purchaseBallsWithSol(3, 2)
+          .accounts(solAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with PurchaseExceedsMax");
+      } catch (err) {
+        assert.ok(err.toString().includes("PurchaseExceedsMax"));
+      }
+    });
+
+    it("non-treasurer cannot withdraw SOL revenue", async () => {
+      try {
+        await program.methods
+          .withdrawSolRevenue(new BN(1))
+          .accounts({
+            authority: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            treasuryConfig: treasuryConfigPda,
+            solTreasury: solTreasuryPda,
+            treasuryWallet: treasuryKeypair.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with Unauthorized");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+
+    it("treasurer withdraws SOL revenue but cannot drain the rent reserve", async () => {
+      const balance = await provider.connection.getBalance(solTreasuryPda);
+      const accounts = {
+        authority: authority.publicKey,
+        gameConfig: gameConfigPda,
+        treasuryConfig: treasuryConfigPda,
+        solTreasury: solTreasuryPda,
+        treasuryWallet: treasuryKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      };
+
+      try {
+        await program.methods
+          .withdrawSolRevenue(new BN(balance))
+          .accounts(accounts)
+          .rpc();
+        assert.fail("Should have failed with InsufficientWithdrawalAmount");
+      } catch (err) {
+        assert.ok(err.toString().includes("InsufficientWithdrawalAmount"));
+      }
+
+      const amount = SOL_BALL_PRICES[2].toNumber();
+      await program.methods
+        .withdrawSolRevenue(new BN(amount))
+        .accounts(accounts)
+        .rpc();
+
+      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.equal(treasuryConfig.totalSolWithdrawn.toNumber(), amount);
+      assert.equal(await provider.connection.getBalance(solTreasuryPda), balance - amount);
+      assert.equal(await provider.connection.getBalance(treasuryKeypair.publicKey), amount);
+    });
+
+    it("rejects a SOL withdrawal to a wallet other than the treasury", async () => {
+      try {
+        await program.methods
+          .withdrawSolRevenue(new BN(1))
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+            treasuryConfig: treasuryConfigPda,
+            solTreasury: solTreasuryPda,
+            treasuryWallet: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed with InvalidTreasuryAccount");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidTreasuryAccount"));
+      }
+    });
+  });
+
+  // ============================================================
+  // ORACLE-PRICED PURCHASES
+  // ============================================================
+
+  describe("purchase_balls_at_oracle_price", () => {
+    // $1.00 Poke Ball at $0.05 per SolBall = 20 SolBalls (6 decimals)
+    const POKE_BALL_COST = 20_000_000;
+
+    const [pendingConfigPda] = PublicKey.findProgramAddressSync(
+      [PENDING_CONFIG_SEED],
+      program.programId
+    );
+
+    const setFeed = async (
+      feed: PublicKey,
+      maxAge = 60,
+      maxConfBps = 200,
+      usdBallPrices = USD_BALL_PRICES
+    ) => {
+      const values = {
+        ...(await currentValues()),
+        usdBallPrices,
+        priceFeed: feed,
+        maxPriceAge: new BN(maxAge),
+        maxPriceConfBps: maxConfBps,
+      };
+      await program.methods
+        .queueConfigChange(values)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .executeConfigChange()
+        .accounts({
+          executor: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+    };
+
+    const purchase = (quantity: number, maxTotalCost: number, priceFeed = MOCK_FEED_FRESH) =>
+      program.methods
+        .purchaseBallsAtOraclePrice(0, quantity, new BN(maxTotalCost))
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          priceFeed,
+          solballsMint,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+    before(async function () {
+      if (!(await provider.connection.getAccountInfo(MOCK_FEED_FRESH))) this.skip();
+    });
+
+    it("rejects out-of-range staleness bounds", async () => {
+      try {
+        await setFeed(MOCK_FEED_FRESH, 0);
+        assert.fail("Should have failed with InvalidPriceBounds");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidPriceBounds"));
+      }
+    });
+
+    it("sets the price feed", async () => {
+      await setFeed(MOCK_FEED_FRESH);
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(gameConfig.priceFeed.equals(MOCK_FEED_FRESH));
+      assert.equal(gameConfig.maxPriceAge.toNumber(), 60);
+      assert.equal(gameConfig.maxPriceConfBps, 200);
+      assert.equal(gameConfig.usdBallPrices[0].toNumber(), USD_BALL_PRICES[0].toNumber());
+    });
+
+    it("converts the USD price at the oracle price", async () => {
+      const quantity = 2;
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      const gameBefore = Number((await getAccount(provider.connection, gameSolballsAta)).amount);
+
+      await purchase(quantity, POKE_BALL_COST * quantity);
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] + quantity);
+
+      const gameAfter = Number((await getAccount(provider.connection, gameSolballsAta)).amount);
+      assert.equal(gameAfter - gameBefore, POKE_BALL_COST * quantity);
+    });
+
+    it("enforces the buyer's slippage bound", async () => {
+      try {
+        await purchase(1, POKE_BALL_COST - 1);
+        assert.fail("Should have failed with SlippageExceeded");
+      } catch (err) {
+        assert.ok(err.toString().includes("SlippageExceeded"));
+      }
+    });
+
+    it("prices purchase_balls from the USD price instead of ball_prices", async () => {
+      const gameBefore = Number((await getAccount(provider.connection, gameSolballsAta)).amount);
+
+      await program.methods
+        .purchaseBalls(0, 1, new BN(POKE_BALL_COST))
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          priceFeed: MOCK_FEED_FRESH,
+          solballsMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const gameAfter = Number((await getAccount(provider.connection, gameSolballsAta)).amount);
+      assert.equal(gameAfter - gameBefore, POKE_BALL_COST);
+    });
+
+    it("enforces the slippage bound on bundles at the oracle price", async () => {
+      try {
+        await program.methods
+          .purchaseBallBundle([2, 0, 0, 0], new BN(POKE_BALL_COST * 2 - 1))
+          .accounts({
+            player: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            playerTokenAccount: playerSolballsAta,
+            gameSolballsAccount: gameSolballsAta,
+            playerInventory: playerInventoryPda,
+            priceFeed: MOCK_FEED_FRESH,
+            solballsMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with SlippageExceeded");
+      } catch (err) {
+        assert.ok(err.toString().includes("SlippageExceeded"));
+      }
+    });
+
+    it("requires the price feed for purchase_balls once USD prices are set", async () => {
+      try {
+        await program.methods
+          .purchaseBalls(0, 1, ANY_COST)
+          .accounts({
+            player: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            playerTokenAccount: playerSolballsAta,
+            gameSolballsAccount: gameSolballsAta,
+            playerInventory: playerInventoryPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with PriceFeedMissing");
+      } catch (err) {
+        assert.ok(err.toString().includes("PriceFeedMissing"));
+      }
+    });
+
+    it("rejects a feed other than the configured one", async () => {
+      try {
+        await purchase(1, POKE_BALL_COST, MOCK_FEED_STALE);
+        assert.fail("Should have failed with InvalidPriceFeed");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidPriceFeed"));
+      }
+    });
+
+    it("rejects a stale price", async () => {
+      await setFeed(MOCK_FEED_STALE);
+      try {
+        await purchase(1, POKE_BALL_COST, MOCK_FEED_STALE);
+        assert.fail("Should have failed with StalePrice");
+      } catch (err) {
+        assert.ok(err.toString().includes("StalePrice"));
+      }
+    });
+
+    it("rejects a feed not owned by the Pyth program", async () => {
+      await setFeed(MOCK_FEED_UNOWNED);
+      try {
+        await purchase(1, POKE_BALL_COST, MOCK_FEED_UNOWNED);
+        assert.fail("Should have failed with InvalidPriceFeed");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidPriceFeed"));
+      }
+    });
+
+    it("rejects a price with a wide confidence interval", async () => {
+      await setFeed(MOCK_FEED_WIDE);
+      try {
+        await purchase(1, POKE_BALL_COST * 2, MOCK_FEED_WIDE);
+        assert.fail("Should have failed with PriceConfidenceTooWide");
+      } catch (err) {
+        assert.ok(err.toString().includes("PriceConfidenceTooWide"));
+      }
+    });
+
+    after(async function () {
+      if (await provider.connection.getAccountInfo(MOCK_FEED_FRESH)) {
+        await setFeed(PublicKey.default, 60, 200, NO_USD_BALL_PRICES);
+      }
+    });
+  });
+
+  // ============================================================
+  // SPEND LIMITS + SELF-EXCLUSION
+  // ============================================================
+
+  describe("spend limits and self-exclusion", () => {
+    const NO_LIMITS = [new BN(0), new BN(0), new BN(0)];
+    const excludedPlayer = Keypair.generate();
+    let playerSpendTrackerPda: PublicKey;
+
+    const purchaseAccounts = (player: PublicKey, tokenAccount: PublicKey) => ({
+      player,
+      gameConfig: gameConfigPda,
+      playerTokenAccount: tokenAccount,
+      gameSolballsAccount: gameSolballsAta,
+      playerInventory: PublicKey.findProgramAddressSync(
+        [PLAYER_INV_SEED, player.toBuffer()],
+        program.programId
+      )[0],
+      spendTracker: PublicKey.findProgramAddressSync(
+        [SPEND_TRACKER_SEED, player.toBuffer()],
+        program.programId
+      )[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      [playerSpendTrackerPda] = PublicKey.findProgramAddressSync(
+        [SPEND_TRACKER_SEED, playerKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("purchases record spend in the player's tracker", async () => {
+      const tracker = await program.account.spendTracker.fetch(playerSpendTrackerPda);
+      assert.ok(tracker.player.equals(playerKeypair.publicKey));
+      assert.ok(tracker.daySpent[0].gtn(0));
+      assert.ok(tracker.weekSpent[0].gte(tracker.daySpent[0]));
+      assert.equal(tracker.excludedUntil.toNumber(), 0);
+    });
+
+    it("rejects a daily limit above the weekly limit", async () => {
+      try {
+        await program.methods
+          .setSpendLimits(
+            [new BN(2_000_000), new BN(0), new BN(0)],
+            [new BN(1_000_000), new BN(0), new BN(0)]
+          )
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+        assert.fail("Should have failed with InvalidSpendLimits");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidSpendLimits"));
+      }
+    });
+
+    it("enforces the daily limit across transactions", async () => {
+      // Allow exactly one more Poke Ball today
+      const tracker = await program.account.spendTracker.fetch(playerSpendTrackerPda);
+      const dailyLimit = tracker.daySpent[0].add(DEFAULT_BALL_PRICES[0]);
+
+      await program.methods
+        .setSpendLimits([dailyLimit, new BN(0), new BN(0)], NO_LIMITS)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(config.dailySpendLimits[0].eq(dailyLimit));
+
+      await program.methods
+        .purchaseBalls(0, 1, ANY_COST)
+        .accounts(purchaseAccounts(playerKeypair.publicKey, playerSolballsAta))
+        .signers([playerKeypair])
+        .rpc();
+
+      try {
+        await program.methods
+          .purchaseBalls(0, 1, ANY_COST)
+          .accounts(purchaseAccounts(playerKeypair.publicKey, playerSolballsAta))
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with DailySpendLimitExceeded");
+      } catch (err) {
+        assert.ok(err.toString().includes("DailySpendLimitExceeded"));
+      }
+    });
+
+    it("self-excluded player cannot purchase", async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        excludedPlayer.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+      const excludedAta = await createAssociatedTokenAccount(
+        provider.connection,
+        (authority as any).payer,
+        solballsMint,
+        excludedPlayer.publicKey
+      );
+
+      const until = Math.floor(Date.now() / 1000) + 30 * 24 * 60 * 60;
+      await program.methods
+        .selfExclude(new BN(until))
+        .accounts({
+          player: excludedPlayer.publicKey,
+          spendTracker: purchaseAccounts(excludedPlayer.publicKey, excludedAta).spendTracker,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([excludedPlayer])
+        .rpc();
+
+      try {
+        await program.methods
+          .purchaseBalls(0, 1, ANY_COST)
+          .accounts(purchaseAccounts(excludedPlayer.publicKey, excludedAta))
+          .signers([excludedPlayer])
+          .rpc();
+        assert.fail("Should have failed with SelfExcluded");
+      } catch (err) {
+        assert.ok(err.toString().includes("SelfExcluded"));
+      }
+    });
+
+    it("self-exclusion cannot be shortened", async () => {
+      const trackerPda = PublicKey.findProgramAddressSync(
+        [SPEND_TRACKER_SEED, excludedPlayer.publicKey.toBuffer()],
+        program.programId
+      )[0];
+      const tracker = await program.account.spendTracker.fetch(trackerPda);
+
+      try {
+        await program.methods
+          .selfExclude(tracker.excludedUntil.subn(1))
+          .accounts({
+            player: excludedPlayer.publicKey,
+            spendTracker: trackerPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([excludedPlayer])
+          .rpc();
+        assert.fail("Should have failed with InvalidSelfExclusion");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidSelfExclusion"));
+      }
+    });
+
+    after(async () => {
+      await program.methods
+        .setSpendLimits(NO_LIMITS, NO_LIMITS)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+    });
+  });
+
+  // ============================================================
+  // GIFTED PURCHASES
+  // ============================================================
+
+  describe("purchase_balls_for", () => {
+    const recipient = Keypair.generate().publicKey;
+    const [recipientInventoryPda] = PublicKey.findProgramAddressSync(
+      [PLAYER_INV_SEED, recipient.toBuffer()],
+      program.programId
+    );
+
+    const giftAccounts = (to: PublicKey = recipient) => ({
+      payer: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      payerTokenAccount: playerSolballsAta,
+      gameSolballsAccount: gameSolballsAta,
+      recipientInventory: PublicKey.findProgramAddressSync(
+        [PLAYER_INV_SEED, to.toBuffer()],
+        program.programId
+      )[0],
+      recipientSpendTracker: PublicKey.findProgramAddressSync(
+        [SPEND_TRACKER_SEED, to.toBuffer()],
+        program.programId
+      )[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    it("sponsor gifts Great Balls to a wallet that has never played", async () => {
+      const payerInventoryBefore = await program.account.playerInventory.fetch(playerInventoryPda);
+      const payerBalanceBefore = Number((await getAccount(provider.connection, playerSolballsAta)).amount);
+
+      await program.methods
+        .purchaseBallsFor(recipient, 1, 2, ANY_COST)
+        .accounts(giftAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const recipientInventory = await program.account.playerInventory.fetch(recipientInventoryPda);
+      assert.ok(recipientInventory.player.equals(recipient));
+      assert.equal(recipientInventory.balls[1], 2);
+      assert.equal(recipientInventory.totalPurchased.toNumber(), 2);
+
+      // Payer is charged but their own inventory is untouched
+      const payerInventoryAfter = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.deepEqual(payerInventoryAfter.balls, payerInventoryBefore.balls);
+      const payerBalanceAfter = Number((await getAccount(provider.connection, playerSolballsAta)).amount);
+      assert.equal(payerBalanceBefore - payerBalanceAfter, DEFAULT_BALL_PRICES[1].toNumber() * 2);
+    });
+
+    it("rejects gifting to yourself", async () => {
+      try {
+        await program.methods
+          .purchaseBallsFor(playerKeypair.publicKey, 0, 1, ANY_COST)
+          .accounts(giftAccounts(playerKeypair.publicKey))
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InvalidRecipient");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRecipient"));
+      }
+    });
+
+    it("rejects gifting to a self-excluded wallet", async () => {
+      const excluded = Keypair.generate();
+      const airdropSig = await provider.connection.requestAirdrop(
+        excluded.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      const until = Math.floor(Date.now() / 1000) + 30 * 24 * 60 * 60;
+      await program.methods
+        .selfExclude(new BN(until))
+        .accounts({
+          player: excluded.publicKey,
+          spendTracker: giftAccounts(excluded.publicKey).recipientSpendTracker,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([excluded])
+        .rpc();
+
+      try {
+        await program.methods
+          .purchaseBallsFor(excluded.publicKey, 0, 1, ANY_COST)
+          .accounts(giftAccounts(excluded.publicKey))
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with RecipientSelfExcluded");
+      } catch (err) {
+        assert.ok(err.toString().includes("RecipientSelfExcluded"));
+      }
+    });
+  });
+
+  // ============================================================
+  // PLAYER-TO-PLAYER TRANSFERS
+  // ============================================================
+
+  describe("transfer_balls", () => {
+    const friend = Keypair.generate().publicKey;
+    const [friendInventoryPda] = PublicKey.findProgramAddressSync(
+      [PLAYER_INV_SEED, friend.toBuffer()],
+      program.programId
+    );
+
+    const transferAccounts = () => ({
+      sender: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      senderInventory: playerInventoryPda,
+      recipientInventory: friendInventoryPda,
+      systemProgram: SystemProgram.programId,
+    });
+
+    it("player sends a Poke Ball to a new wallet", async () => {
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+
+      await program.methods
+        .transferBalls(friend, 0, 1)
+        .accounts(transferAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] - 1);
+
+      const friendInventory = await program.account.playerInventory.fetch(friendInventoryPda);
+      assert.ok(friendInventory.player.equals(friend));
+      assert.equal(friendInventory.balls[0], 1);
+      // Transfers are not purchases
+      assert.equal(friendInventory.totalPurchased.toNumber(), 0);
+    });
+
+    it("fails when sending more balls than owned", async () => {
+      const inventory = await program.account.playerInventory.fetch(playerInventoryPda);
+      try {
+        await program.methods
+          .transferBalls(friend, 3, inventory.balls[3] + 1)
+          .accounts(transferAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InsufficientBalls");
+      } catch (err) {
+        assert.ok(err.toString().includes("InsufficientBalls"));
+      }
+    });
+
+    it("is blocked while transfers are paused", async () => {
+      await program.methods
+        .setPauseFlags(PAUSE_TRANSFERS)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .transferBalls(friend, 0, 1)
+          .accounts(transferAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with TransfersPaused");
+      } catch (err) {
+        assert.ok(err.toString().includes("TransfersPaused"));
+      } finally {
+        await program.methods
+          .setPauseFlags(0)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+      }
+    });
+  });
+
+  // ============================================================
+  // TOKENIZED BALLS
+  // ============================================================
+
+  describe("ball tokens", () => {
+    const [pokeBallMintPda] = PublicKey.findProgramAddressSync(
+      [BALL_MINT_SEED, Buffer.from([0])],
+      program.programId
+    );
+    let playerBallAta: PublicKey;
+
+    const ballTokenAccounts = () => ({
+      player: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      playerInventory: playerInventoryPda,
+      ballMint: pokeBallMintPda,
+      playerBallAccount: playerBallAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      playerBallAta = await getAssociatedTokenAddress(
+        pokeBallMintPda,
+        playerKeypair.publicKey
+      );
+    });
+
+    it("config admin creates the Poke Ball mint", async () => {
+      await program.methods
+        .createBallMint(0)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          ballMint: pokeBallMintPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(config.ballMints[0].equals(pokeBallMintPda));
+      assert.ok(config.ballMints[1].equals(PublicKey.default));
+
+      const mint = await getMint(provider.connection, pokeBallMintPda);
+      assert.equal(mint.decimals, 0);
+      assert.ok(mint.mintAuthority.equals(gameConfigPda));
+      assert.isNull(mint.freezeAuthority);
+    });
+
+    it("player withdraws Poke Balls as tokens", async () => {
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+
+      await program.methods
+        .withdrawBallTokens(0, 2)
+        .accounts(ballTokenAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] - 2);
+      const tokenAccount = await getAccount(provider.connection, playerBallAta);
+      assert.equal(Number(tokenAccount.amount), 2);
+    });
+
+    it("player redeems a token back into inventory", async () => {
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+
+      await program.methods
+        .redeemBallTokens(0, 1)
+        .accounts(ballTokenAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] + 1);
+      assert.equal(after.totalPurchased.toNumber(), before.totalPurchased.toNumber());
+      const tokenAccount = await getAccount(provider.connection, playerBallAta);
+      assert.equal(Number(tokenAccount.amount), 1);
+    });
+
+    it("purchase_balls can deliver ball tokens", async () => {
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+
+      await program.methods
+        .purchaseBalls(0, 3, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          ballMint: pokeBallMintPda,
+          playerBallAccount: playerBallAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0]);
+      assert.equal(after.totalPurchased.toNumber(), before.totalPurchased.toNumber() + 3);
+      const tokenAccount = await getAccount(provider.connection, playerBallAta);
+      assert.equal(Number(tokenAccount.amount), 4);
+    });
+
+    it("withdrawals and redeems are blocked while ball tokens are paused", async () => {
+      await program.methods
+        .setPauseFlags(PAUSE_BALL_TOKENS)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        try {
+          await program.methods
+            .withdrawBallTokens(0, 1)
+            .accounts(ballTokenAccounts())
+            .signers([playerKeypair])
+            .rpc();
+          assert.fail("Should have failed with BallTokensPaused");
+        } catch (err) {
+          assert.ok(err.toString().includes("BallTokensPaused"));
+        }
+
+        try {
+          await program.methods
+            .redeemBallTokens(0, 1)
+            .accounts(ballTokenAccounts())
+            .signers([playerKeypair])
+            .rpc();
+          assert.fail("Should have failed with BallTokensPaused");
+        } catch (err) {
+          assert.ok(err.toString().includes("BallTokensPaused"));
+        }
+      } finally {
+        await program.methods
+          .setPauseFlags(0)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+      }
+    });
+  });
+
+  // ============================================================
+  // CRAFTING
+  // ============================================================
+
+  describe("craft_balls", () => {
+    const craftAccounts = () => ({
+      player: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      playerInventory: playerInventoryPda,
+    });
+
+    it("initializes with the default craft ratios", async () => {
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.deepEqual(config.craftRatios, [10, 3, 2]);
+    });
+
+    it("player crafts a Great Ball from Poke Balls", async () => {
+      // Make sure there are enough Poke Balls to craft with
+      await program.methods
+        .purchaseBalls(0, 10, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+
+      await program.methods
+        .craftBalls(0, 1)
+        .accounts(craftAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] - 10);
+      assert.equal(after.balls[1], before.balls[1] + 1);
+      assert.equal(after.totalPurchased.toNumber(), before.totalPurchased.toNumber());
+    });
+
+    it("fails without enough balls of the lower tier", async () => {
+      const inventory = await program.account.playerInventory.fetch(playerInventoryPda);
+      try {
+        await program.methods
+          .craftBalls(2, Math.floor(inventory.balls[2] / 2) + 1)
+          .accounts(craftAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InsufficientBalls");
+      } catch (err) {
+        assert.ok(err.toString().includes("InsufficientBalls"));
+      }
+    });
+
+    it("Master Balls cannot be crafted further", async () => {
+      try {
+        await program.methods
+          .craftBalls(3, 1)
+          .accounts(craftAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with CraftNotAvailable");
+      } catch (err) {
+        assert.ok(err.toString().includes("CraftNotAvailable"));
+      }
+    });
+
+    it("rejects a 1:1 craft ratio", async () => {
+      try {
+        await program.methods
+          .setCraftRatios([1, 3, 2])
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+        assert.fail("Should have failed with InvalidCraftRatio");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidCraftRatio"));
+      }
+    });
+
+    it("disabling a recipe blocks it", async () => {
+      await program.methods
+        .setCraftRatios([0, 3, 2])
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .craftBalls(0, 1)
+          .accounts(craftAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with CraftNotAvailable");
+      } catch (err) {
+        assert.ok(err.toString().includes("CraftNotAvailable"));
+      } finally {
+        await program.methods
+          .setCraftRatios([10, 3, 2])
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+      }
+    });
+
+    it("is blocked while crafting is paused", async () => {
+      await program.methods
+        .setPauseFlags(PAUSE_CRAFTING)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .craftBalls(0, 1)
+          .accounts(craftAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with CraftingPaused");
+      } catch (err) {
+        assert.ok(err.toString().includes("CraftingPaused"));
+      } finally {
+        await program.methods
+          .setPauseFlags(0)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+      }
+    });
+  });
+
+  // ============================================================
+  // SELL-BACK / REFUND WINDOW
+  // ============================================================
+
+  describe("sell_back_balls", () => {
+    const DEFAULT_REFUND_WINDOW = 15 * 60;
+    const DEFAULT_REFUND_HAIRCUT_BPS = 1_000;
+
+    const sellBackAccounts = () => ({
+      player: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      playerInventory: playerInventoryPda,
+      playerTokenAccount: playerSolballsAta,
+      gameSolballsAccount: gameSolballsAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    const [pendingConfigPda] = PublicKey.findProgramAddressSync(
+      [PENDING_CONFIG_SEED],
+      program.programId
+    );
+
+    const setRefundPolicy = async (window: number, haircutBps: number) => {
+      const values = {
+        ...(await currentValues()),
+        refundWindow: new BN(window),
+        refundHaircutBps: haircutBps,
+      };
+      await program.methods
+        .queueConfigChange(values)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .executeConfigChange()
+        .accounts({
+          executor: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+    };
+
+    it("initializes with the default refund policy", async () => {
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(config.refundWindow.toNumber(), DEFAULT_REFUND_WINDOW);
+      assert.equal(config.refundHaircutBps, DEFAULT_REFUND_HAIRCUT_BPS);
+    });
+
+    it("player sells back a freshly bought Great Ball minus the haircut", async () => {
+      await program.methods
+        .purchaseBalls(1, 2, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.ok(before.refundableBalls[1] >= 2);
+      const configBefore = await program.account.gameConfig.fetch(gameConfigPda);
+      const balanceBefore = Number((await getAccount(provider.connection, playerSolballsAta)).amount);
+
+      await program.methods
+        .sellBackBalls(1, 1)
+        .accounts(sellBackAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      // 10 SolBalls paid, 10% kept
+      const expectedRefund = DEFAULT_BALL_PRICES[1].toNumber() * 9 / 10;
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[1], before.balls[1] - 1);
+      assert.equal(after.refundableBalls[1], before.refundableBalls[1] - 1);
+      assert.equal(after.totalPurchased.toNumber(), before.totalPurchased.toNumber() - 1);
+
+      const balanceAfter = Number((await getAccount(provider.connection, playerSolballsAta)).amount);
+      assert.equal(balanceAfter - balanceBefore, expectedRefund);
+
+      const configAfter = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(
+        configBefore.totalRevenue.toNumber() - configAfter.totalRevenue.toNumber(),
+        expectedRefund
+      );
+    });
+
+    it("fails for more balls than were bought in the window", async () => {
+      const inventory = await program.account.playerInventory.fetch(playerInventoryPda);
+      try {
+        await program.methods
+          .sellBackBalls(1, inventory.refundableBalls[1] + 1)
+          .accounts(sellBackAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with ExceedsRefundable");
+      } catch (err) {
+        assert.ok(err.toString().includes("ExceedsRefundable"));
+      }
+    });
+
+    it("balls given away are no longer refundable", async () => {
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.ok(before.refundableBalls[1] >= 1);
+      const friend = Keypair.generate().publicKey;
+
+      await program.methods
+        .transferBalls(friend, 1, 1)
+        .accounts({
+          sender: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          senderInventory: playerInventoryPda,
+          recipientInventory: PublicKey.findProgramAddressSync(
+            [PLAYER_INV_SEED, friend.toBuffer()],
+            program.programId
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.refundableBalls[1], before.refundableBalls[1] - 1);
+      // Cost shrinks pro-rata, rounding the removed share down
+      const costBefore = before.refundableCost[1].toNumber();
+      assert.equal(
+        after.refundableCost[1].toNumber(),
+        costBefore - Math.floor(costBefore / before.refundableBalls[1])
+      );
+    });
+
+    it("sell-back is blocked while purchases are paused", async () => {
+      await program.methods
+        .setPauseFlags(PAUSE_PURCHASES)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .sellBackBalls(1, 1)
+          .accounts(sellBackAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with PurchasesPaused");
+      } catch (err) {
+        assert.ok(err.toString().includes("PurchasesPaused"));
+      } finally {
+        await program.methods
+          .setPauseFlags(0)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+      }
+    });
+
+    it("an open refund window cannot be expired", async () => {
+      try {
+        await program.methods
+          .expireRefunds()
+          .accounts({
+            gameConfig: gameConfigPda,
+            playerInventory: playerInventoryPda,
+          })
+          .rpc();
+        assert.fail("Should have failed with RefundWindowOpen");
+      } catch (err) {
+        assert.ok(err.toString().includes("RefundWindowOpen"));
+      }
+    });
+
+    it("rejects a haircut above 100%", async () => {
+      try {
+        await setRefundPolicy(DEFAULT_REFUND_WINDOW, 10_001);
+        assert.fail("Should have failed with InvalidRefundPolicy");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRefundPolicy"));
+      }
+    });
+
+    it("a zero refund window disables sell-back", async () => {
+      await setRefundPolicy(0, DEFAULT_REFUND_HAIRCUT_BPS);
+      try {
+        await program.methods
+          .sellBackBalls(1, 1)
+          .accounts(sellBackAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with RefundsDisabled");
+      } catch (err) {
+        assert.ok(err.toString().includes("RefundsDisabled"));
+      } finally {
+        await setRefundPolicy(DEFAULT_REFUND_WINDOW, DEFAULT_REFUND_HAIRCUT_BPS);
+      }
+    });
+
+    it("anyone expires a lapsed window, releasing its SolBalls for distribution", async () => {
+      await program.methods
+        .purchaseBalls(1, 1, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      // A zero window closes every open window
+      await setRefundPolicy(0, DEFAULT_REFUND_HAIRCUT_BPS);
+      try {
+        const inventoryBefore = await program.account.playerInventory.fetch(playerInventoryPda);
+        const configBefore = await program.account.gameConfig.fetch(gameConfigPda);
+        const held = inventoryBefore.refundableCost.reduce((sum, cost) => sum + cost.toNumber(), 0);
+        assert.isAbove(held, 0);
+
+        await program.methods
+          .expireRefunds()
+          .accounts({
+            gameConfig: gameConfigPda,
+            playerInventory: playerInventoryPda,
+          })
+          .rpc();
+
+        const inventoryAfter = await program.account.playerInventory.fetch(playerInventoryPda);
+        assert.deepEqual(inventoryAfter.refundableBalls, [0, 0, 0, 0]);
+        const configAfter = await program.account.gameConfig.fetch(gameConfigPda);
+        assert.equal(
+          configBefore.outstandingRefundable.toNumber() - configAfter.outstandingRefundable.toNumber(),
+          held
+        );
+      } finally {
+        await setRefundPolicy(DEFAULT_REFUND_WINDOW, DEFAULT_REFUND_HAIRCUT_BPS);
+      }
+    });
+  });
+
+  // ============================================================
+  // ACCOUNT MIGRATIONS
+  // ============================================================
+
+  describe("account migrations", () => {
+    it("migrate_game_config fails on an account already on the current layout", async () => {
+      try {
+        await program.methods
+          .migrateGameConfig()
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+            treasuryConfig: treasuryConfigPda,
+            solTreasury: solTreasuryPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed as already migrated");
+      } catch (err) {
+        assert.ok(err.toString().includes("AccountAlreadyMigrated"));
+      }
+    });
+
+    it("migrate_player_inventory fails on an account already on the current layout", async () => {
+      try {
+        await program.methods
+          .migratePlayerInventory(playerKeypair.publicKey)
+          .accounts({
+            payer: authority.publicKey,
+            playerInventory: playerInventoryPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed as already migrated");
+      } catch (err) {
+        assert.ok(err.toString().includes("AccountAlreadyMigrated"));
+      }
+    });
+
+    it("migrate_vrf_request rejects a non-VrfRequest account", async () => {
+      try {
+        await program.methods
+          .migrateVrfRequest()
+          .accounts({
+            payer: authority.publicKey,
+            vrfRequest: nftVaultPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed on the wrong account type");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidAccountLayout"));
+      }
+    });
+  });
+
+  // ============================================================
+  // POKEMON SLOTS MIGRATION
+  // ============================================================
+
+  describe("migrate_pokemon_slots", () => {
+    it("fails on an account already on the rarity layout", async () => {
+      try {
+        await program.methods
+          .migratePokemonSlots()
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed as already migrated");
+      } catch (err) {
+        assert.ok(err.toString().includes("AccountAlreadyMigrated"));
+      }
+    });
+
+    it("fails for non-authority signer", async () => {
+      try {
+        await program.methods
+          .migratePokemonSlots()
+          .accounts({
+            authority: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed for non-authority");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+  });
+
+  // ============================================================
+  // PAUSE / CIRCUIT BREAKER
+  // ============================================================
+
+  describe("pause", () => {
+    it("pauses purchases and rejects purchase_balls", async () => {
+      await program.methods
+        .setPauseFlags(PAUSE_PURCHASES)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.pauseFlags, PAUSE_PURCHASES);
+
+      try {
+        await program.methods
+          .purchaseBalls(0, 1, ANY_COST)
+          .accounts({
+            player: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            playerTokenAccount: playerSolballsAta,
+            gameSolballsAccount: gameSolballsAta,
+            playerInventory: playerInventoryPda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed while purchases are paused");
+      } catch (err) {
+        assert.ok(err.toString().includes("PurchasesPaused"));
+      }
+    });
+
+    it("pauses spawns independently of purchases", async () => {
+      await program.methods
+        .setPauseFlags(PAUSE_SPAWNS)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .forceSpawnPokemon(3, 100, 100, RARITY_COMMON)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+          })
+          .rpc();
+        assert.fail("Should have failed while spawns are paused");
+      } catch (err) {
+        assert.ok(err.toString().includes("SpawnsPaused"));
+      }
+    });
+
+    it("fails with unknown pause flags", async () => {
+      try {
+        await program.methods
+          .setPauseFlags(1 << 7)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+        assert.fail("Should have failed with invalid pause flags");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidPauseFlags"));
+      }
+    });
+
+    it("fails for non-authority", async () => {
+      try {
+        await program.methods
+          .setPauseFlags(PAUSE_THROWS | PAUSE_NFT_AWARDS)
+          .accounts({
+            authority: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed for non-authority");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized") || err.toString().includes("ConstraintRaw"));
+      }
+    });
+
+    it("unpauses everything", async () => {
+      await program.methods
+        .setPauseFlags(0)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.pauseFlags, 0);
+    });
+  });
+
+  // ============================================================
+  // AUTHORITY TRANSFER
+  // ============================================================
+
+  describe("authority transfer", () => {
+    const newAuthority = Keypair.generate();
+
+    it("proposes and cancels an authority transfer", async () => {
+      await program.methods
+        .proposeAuthority(newAuthority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      let gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(gameConfig.pendingAuthority.equals(newAuthority.publicKey));
+
+      await program.methods
+        .cancelAuthorityTransfer()
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(gameConfig.pendingAuthority.equals(PublicKey.default));
+    });
+
+    it("fails to accept when signer is not the pending authority", async () => {
+      await program.methods
+        .proposeAuthority(newAuthority.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accounts({
+            newAuthority: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            nftVault: nftVaultPda,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed for non-pending signer");
+      } catch (err) {
+        assert.ok(err.toString().includes("NotPendingAuthority"));
+      }
+    });
+
+    it("pending authority accepts and vault authority moves with it", async () => {
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          newAuthority: newAuthority.publicKey,
+          gameConfig: gameConfigPda,
+          nftVault: nftVaultPda,
+        })
+        .signers([newAuthority])
+        .rpc();
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(gameConfig.authority.equals(newAuthority.publicKey));
+      assert.ok(gameConfig.pendingAuthority.equals(PublicKey.default));
+
+      const nftVault = await program.account.nftVault.fetch(nftVaultPda);
+      assert.ok(nftVault.authority.equals(newAuthority.publicKey));
+    });
+
+    it("transfers authority back for remaining tests", async () => {
+      await program.methods
+        .proposeAuthority(authority.publicKey)
+        .accounts({
+          authority: newAuthority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .signers([newAuthority])
+        .rpc();
+
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          newAuthority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          nftVault: nftVaultPda,
+        })
+        .rpc();
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(gameConfig.authority.equals(authority.publicKey));
+    });
+  });
+
+  // ============================================================
+  // ROLES
+  // ============================================================
+
+  describe("roles", () => {
+    const spawnBot = Keypair.generate();
+
+    before(async () => {
+      // Fund the bot so rent-paying instructions reach the role check
+      const airdropSig = await provider.connection.requestAirdrop(
+        spawnBot.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+    });
+
+    it("grants the Spawner role and the holder can spawn", async () => {
+      await program.methods
+        .grantRole(ROLE_SPAWNER, spawnBot.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(gameConfig.roles[ROLE_SPAWNER].equals(spawnBot.publicKey));
+
+      await program.methods
+        .forceSpawnPokemon(15, 300, 300, RARITY_COMMON)
+        .accounts({
+          authority: spawnBot.publicKey,
+          gameConfig: gameConfigPda,
+          pokemonSlots: pokemonSlotsPda,
+        })
+        .signers([spawnBot])
+        .rpc();
+
+      await program.methods
+        .despawnPokemon(15)
+        .accounts({
+          authority: spawnBot.publicKey,
+          gameConfig: gameConfigPda,
+          pokemonSlots: pokemonSlotsPda,
+        })
+        .signers([spawnBot])
+        .rpc();
+    });
+
+    it("Spawner cannot change configuration", async () => {
+      const [pendingConfigPda] = PublicKey.findProgramAddressSync(
+        [PENDING_CONFIG_SEED],
+        program.programId
+      );
+      try {
+        await program.methods
+          .queueConfigChange({ ...(await currentValues()), maxActivePokemon: 5 })
+          .accounts({
+            authority: spawnBot.publicKey,
+            gameConfig: gameConfigPda,
+            pendingConfigChange: pendingConfigPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([spawnBot])
+          .rpc();
+        assert.fail("Should have failed without ConfigAdmin role");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+
+    it("fails to grant an unknown role", async () => {
+      try {
+        await program.methods
+          .grantRole(4, spawnBot.publicKey)
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+        assert.fail("Should have failed with invalid role");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRole"));
+      }
+    });
+
+    it("revokes the Spawner role", async () => {
+      await program.methods
+        .revokeRole(ROLE_SPAWNER)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .forceSpawnPokemon(15, 300, 300, RARITY_COMMON)
+          .accounts({
+            authority: spawnBot.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+          })
+          .signers([spawnBot])
+          .rpc();
+        assert.fail("Should have failed after revoke");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+  });
+
+  // ============================================================
+  // WITHDRAW REVENUE
+  // ============================================================
+
+  describe("withdraw_revenue", () => {
+    let treasurySolballsAta: PublicKey;
+
+    const withdrawAccounts = (destination: PublicKey) => ({
+      authority: authority.publicKey,
+      gameConfig: gameConfigPda,
+      treasuryConfig: treasuryConfigPda,
+      solballsMint,
+      gameSolballsAccount: gameSolballsAta,
+      treasurySolballsAccount: destination,
+      prizePool: prizePoolPda,
+      reserve: reservePda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      treasurySolballsAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          (authority as any).payer,
+          solballsMint,
+          treasuryKeypair.publicKey
+        )
+      ).address;
+    });
+
+    it("treasurer withdraws SolBalls revenue through the split", async () => {
+      // First, get the current game SolBalls balance
+      const gameTokenAccount = await getAccount(provider.connection, gameSolballsAta);
+      const balance = Number(gameTokenAccount.amount);
+      const { outstandingRefundable } = await program.account.gameConfig.fetch(gameConfigPda);
+      const available = balance - outstandingRefundable.toNumber();
+      assert.isAbove(available, 0, "Game should have non-refundable SolBalls from purchases");
+
+      const withdrawAmount = Math.floor(available / 2); // Withdraw half of what is not refundable
+
+      await program.methods
+        .withdrawRevenue(new BN(withdrawAmount))
+        .accounts(withdrawAccounts(treasurySolballsAta))
+        .rpc();
+
+      // Default 3/96/1 split: only the treasury share reaches the treasury wallet
+      const toTreasury = Math.floor((withdrawAmount * 300) / 10_000);
+      const toReserve = Math.floor((withdrawAmount * 100) / 10_000);
+      const toPrizePool = withdrawAmount - toTreasury - toReserve;
+
+      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.equal(treasuryConfig.totalWithdrawn.toNumber(), toTreasury);
+      assert.equal(treasuryConfig.totalToTreasury.toNumber(), toTreasury);
+      assert.equal(treasuryConfig.totalToPrizePool.toNumber(), toPrizePool);
+      assert.equal(treasuryConfig.totalToReserve.toNumber(), toReserve);
+
+      const received = await getAccount(provider.connection, treasurySolballsAta);
+      assert.equal(Number(received.amount), toTreasury);
+      assert.equal(Number((await getAccount(provider.connection, prizePoolPda)).amount), toPrizePool);
+      assert.equal(Number((await getAccount(provider.connection, reservePda)).amount), toReserve);
+      assert.equal(
+        Number((await getAccount(provider.connection, gameSolballsAta)).amount),
+        balance - withdrawAmount
+      );
+    });
+
+    it("rejects a destination not owned by the treasury wallet", async () => {
+      const authoritySolballsAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          (authority as any).payer,
+          solballsMint,
+          authority.publicKey
+        )
+      ).address;
+
+      try {
+        await program.methods
+          .withdrawRevenue(new BN(1))
+          .accounts(withdrawAccounts(authoritySolballsAta))
+          .rpc();
+        assert.fail("Should have failed with InvalidTreasuryAccount");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidTreasuryAccount"));
+      }
+    });
+
+    it("fails with zero withdrawal", async () => {
+      try {
+        await program.methods
+          .withdrawRevenue(new BN(0))
+          .accounts(withdrawAccounts(treasurySolballsAta))
+          .rpc();
+        assert.fail("Should have failed with zero amount");
+      } catch (err) {
+        assert.ok(err.toString().includes("InsufficientWithdrawalAmount"));
+      }
+    });
+
+    it("fails for non-authority", async () => {
+      try {
+        await program.methods
+          .withdrawRevenue(new BN(1))
+          .accounts({
+            ...withdrawAccounts(treasurySolballsAta),
+            authority: playerKeypair.publicKey,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed for non-authority");
+      } catch (err) {
+        assert.ok(err);
+      }
+    });
+  });
+
+  // ============================================================
+  // SET TREASURY
+  // ============================================================
+
+  describe("set_treasury", () => {
+    const setTreasury = (treasury: PublicKey, signer?: Keypair) =>
+      program.methods
+        .setTreasury(treasury)
+        .accounts({
+          authority: signer ? signer.publicKey : authority.publicKey,
+          gameConfig: gameConfigPda,
+          treasuryConfig: treasuryConfigPda,
+        })
+        .signers(signer ? [signer] : [])
+        .rpc();
+
+    it("non-authority cannot change the treasury", async () => {
+      try {
+        await setTreasury(playerKeypair.publicKey, playerKeypair);
+        assert.fail("Should have failed with Unauthorized");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+
+    it("rejects the default pubkey", async () => {
+      try {
+        await setTreasury(PublicKey.default);
+        assert.fail("Should have failed with InvalidTreasury");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidTreasury"));
+      }
+    });
+
+    it("updates the treasury wallet", async () => {
+      const newTreasury = Keypair.generate().publicKey;
+      await setTreasury(newTreasury);
+
+      let treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.ok(treasuryConfig.treasuryWallet.equals(newTreasury));
+
+      // Restore for the remaining tests
+      await setTreasury(treasuryKeypair.publicKey);
+      treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.ok(treasuryConfig.treasuryWallet.equals(treasuryKeypair.publicKey));
+    });
+  });
+
+  // ============================================================
+  // REVENUE SPLIT
+  // ============================================================
+
+  describe("distribute_revenue", () => {
+    let treasurySolballsAta: PublicKey;
+
+    const setSplit = (splitBps: number[], signer?: Keypair) =>
+      program.methods
+        .setRevenueSplit(splitBps)
+        .accounts({
+          authority: signer ? signer.publicKey : authority.publicKey,
+          gameConfig: gameConfigPda,
+          treasuryConfig: treasuryConfigPda,
+        })
+        .signers(signer ? [signer] : [])
+        .rpc();
+
+    const distributeAccounts = () => ({
+      caller: playerKeypair.publicKey,
+      gameConfig: gameConfigPda,
+      treasuryConfig: treasuryConfigPda,
+      solballsMint,
+      gameSolballsAccount: gameSolballsAta,
+      treasurySolballsAccount: treasurySolballsAta,
+      prizePool: prizePoolPda,
+      reserve: reservePda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      treasurySolballsAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          (authority as any).payer,
+          solballsMint,
+          treasuryKeypair.publicKey
+        )
+      ).address;
+    });
+
+    it("defaults to a 3/96/1 split", async () => {
+      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.deepEqual(treasuryConfig.splitBps, [300, 9600, 100]);
+    });
+
+    it("rejects a split that does not sum to 10,000 bps", async () => {
+      try {
+        await setSplit([300, 9600, 200]);
+        assert.fail("Should have failed with InvalidRevenueSplit");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRevenueSplit"));
+      }
+    });
+
+    it("non-authority cannot change the split", async () => {
+      try {
+        await setSplit([1000, 8000, 1000], playerKeypair);
+        assert.fail("Should have failed with Unauthorized");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+
+    it("a distribution leaves enough SolBalls for sell-back", async () => {
+      await program.methods
+        .purchaseBalls(1, 1, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      await program.methods
+        .distributeRevenue()
+        .accounts(distributeAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const { outstandingRefundable } = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.isAbove(outstandingRefundable.toNumber(), 0);
+      assert.equal(
+        Number((await getAccount(provider.connection, gameSolballsAta)).amount),
+        outstandingRefundable.toNumber()
+      );
+
+      const balanceBefore = Number((await getAccount(provider.connection, playerSolballsAta)).amount);
+      await program.methods
+        .sellBackBalls(1, 1)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerInventory: playerInventoryPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const balanceAfter = Number((await getAccount(provider.connection, playerSolballsAta)).amount);
+      assert.isAbove(balanceAfter, balanceBefore);
+    });
+
+    it("anyone distributes the non-refundable game balance per the split", async () => {
+      await setSplit([1000, 8500, 500]);
+
+      const { outstandingRefundable } = await program.account.gameConfig.fetch(gameConfigPda);
+      const held = outstandingRefundable.toNumber();
+      const balance =
+        Number((await getAccount(provider.connection, gameSolballsAta)).amount) - held;
+      assert.isAbove(balance, 0);
+      const tokenBalance = async (account: PublicKey) =>
+        Number((await getAccount(provider.connection, account)).amount);
+      const treasuryBefore = await tokenBalance(treasurySolballsAta);
+      const prizePoolBefore = await tokenBalance(prizePoolPda);
+      const reserveBefore = await tokenBalance(reservePda);
+      const totalsBefore = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+
+      await program.methods
+        .distributeRevenue()
+        .accounts(distributeAccounts())
+        .signers([playerKeypair])
+        .rpc();
+
+      const toTreasury = Math.floor((balance * 1000) / 10_000);
+      const toReserve = Math.floor((balance * 500) / 10_000);
+      const toPrizePool = balance - toTreasury - toReserve;
+
+      assert.equal(await tokenBalance(gameSolballsAta), held);
+      assert.equal((await tokenBalance(treasurySolballsAta)) - treasuryBefore, toTreasury);
+      assert.equal((await tokenBalance(prizePoolPda)) - prizePoolBefore, toPrizePool);
+      assert.equal((await tokenBalance(reservePda)) - reserveBefore, toReserve);
+
+      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.equal(
+        treasuryConfig.totalToTreasury.toNumber() - totalsBefore.totalToTreasury.toNumber(),
+        toTreasury
+      );
+      assert.equal(
+        treasuryConfig.totalToPrizePool.toNumber() - totalsBefore.totalToPrizePool.toNumber(),
+        toPrizePool
+      );
+      assert.equal(
+        treasuryConfig.totalToReserve.toNumber() - totalsBefore.totalToReserve.toNumber(),
+        toReserve
+      );
+    });
+
+    it("fails when there is nothing to distribute", async () => {
+      try {
+        await program.methods
+          .distributeRevenue()
+          .accounts(distributeAccounts())
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with NothingToDistribute");
+      } catch (err) {
+        assert.ok(err.toString().includes("NothingToDistribute"));
+      }
+    });
+
+    it("rejects a treasury account not owned by the treasury wallet", async () => {
+      try {
+        await program.methods
+          .distributeRevenue()
+          .accounts({ ...distributeAccounts(), treasurySolballsAccount: playerSolballsAta })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InvalidTreasuryAccount");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidTreasuryAccount"));
+      }
+    });
+
+    const prizePoolAccounts = (signer: PublicKey) => ({
+      authority: signer,
+      gameConfig: gameConfigPda,
+      treasuryConfig: treasuryConfigPda,
+      prizePool: prizePoolPda,
+      treasurySolballsAccount: treasurySolballsAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    const reserveAccounts = (signer: PublicKey) => ({
+      authority: signer,
+      gameConfig: gameConfigPda,
+      treasuryConfig: treasuryConfigPda,
+      reserve: reservePda,
+      treasurySolballsAccount: treasurySolballsAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    it("non-treasurer cannot withdraw from the prize pool or reserve", async () => {
+      try {
+        await program.methods
+          .withdrawPrizePool(new BN(1))
+          .accounts(prizePoolAccounts(playerKeypair.publicKey))
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with Unauthorized");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+
+      try {
+        await program.methods
+          .withdrawReserve(new BN(1))
+          .accounts(reserveAccounts(playerKeypair.publicKey))
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with Unauthorized");
+      } catch (err) {
+        assert.ok(err.toString().includes("Unauthorized"));
+      }
+    });
+
+    it("treasurer withdraws the prize pool and reserve to the treasury wallet", async () => {
+      const tokenBalance = async (account: PublicKey) =>
+        Number((await getAccount(provider.connection, account)).amount);
+      const prizePool = await tokenBalance(prizePoolPda);
+      const reserve = await tokenBalance(reservePda);
+      const treasuryBefore = await tokenBalance(treasurySolballsAta);
+      assert.isAbove(prizePool, 0);
+      assert.isAbove(reserve, 0);
+
+      await program.methods
+        .withdrawPrizePool(new BN(prizePool))
+        .accounts(prizePoolAccounts(authority.publicKey))
+        .rpc();
+      await program.methods
+        .withdrawReserve(new BN(reserve))
+        .accounts(reserveAccounts(authority.publicKey))
+        .rpc();
+
+      assert.equal(await tokenBalance(prizePoolPda), 0);
+      assert.equal(await tokenBalance(reservePda), 0);
+      assert.equal((await tokenBalance(treasurySolballsAta)) - treasuryBefore, prizePool + reserve);
+
+      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
+      assert.equal(treasuryConfig.totalFromPrizePool.toNumber(), prizePool);
+      assert.equal(treasuryConfig.totalFromReserve.toNumber(), reserve);
+    });
+
+    it("cannot withdraw more than a bucket holds", async () => {
+      try {
+        await program.methods
+          .withdrawPrizePool(new BN(1))
+          .accounts(prizePoolAccounts(authority.publicKey))
+          .rpc();
+        assert.fail("Should have failed with InsufficientWithdrawalAmount");
+      } catch (err) {
+        assert.ok(err.toString().includes("InsufficientWithdrawalAmount"));
+      }
+    });
+
+    after(async () => {
+      await setSplit([300, 9600, 100]);
+    });
+  });
+
+  // ============================================================
+  // THROW BALL (VRF Integration)
+  // Note: These tests require ORAO VRF devnet to be available.
+  // They validate the instruction structure but VRF fulfillment
+  // depends on the ORAO network being live on devnet.
+  // ============================================================
+
+  describe("throw_ball (VRF)", () => {
+    it("validates slot is active before throw", async () => {
+      // Slot 2 has no Pokemon
+      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
+
+      // Derive VRF request PDA
+      const [vrfRequestPda] = PublicKey.findProgramAddressSync(
+        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      // Derive VRF seed to compute randomness PDA
+      const seed = makeVrfSeed(vrfCounter.toNumber(), 1); // VRF_TYPE_THROW
+      const [vrfRandomnessPda] = PublicKey.findProgramAddressSync(
+        [RANDOMNESS_ACCOUNT_SEED, seed],
+        ORAO_VRF_PROGRAM_ID
+      );
+
+      // Derive ORAO VRF config PDA
+      const [vrfConfigPda] = PublicKey.findProgramAddressSync(
+        [CONFIG_ACCOUNT_SEED],
+        ORAO_VRF_PROGRAM_ID
+      );
+
+      try {
+        await program.methods
+          .throwBall(2, 0) // Slot 2 is empty
+          .accounts({
+            player: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+            playerInventory: playerInventoryPda,
+            vrfRequest: vrfRequestPda,
+            vrfConfig: vrfConfigPda,
+            vrfRandomness: vrfRandomnessPda,
+            vrfTreasury: vrfConfigPda, // Placeholder — real test needs ORAO treasury
+            oraoVrf: ORAO_VRF_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed on inactive slot");
+      } catch (err) {
+        assert.ok(
+          err.toString().includes("SlotNotActive") ||
+          err.toString().includes("Error") // VRF CPI may fail on devnet if not set up
+        );
+      }
+    });
+
+    it("validates player has balls before throw", async () => {
+      // Create a new player with no balls
+      const noBallsPlayer = Keypair.generate();
+      const airdropSig = await provider.connection.requestAirdrop(
+        noBallsPlayer.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      // Create inventory PDA (won't have any balls)
+      const [noBallsInventory] = PublicKey.findProgramAddressSync(
+        [PLAYER_INV_SEED, noBallsPlayer.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // This will fail because the player has no inventory (account doesn't exist yet)
+      // and the throw instruction doesn't use init_if_needed for inventory
+      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
+      const [vrfRequestPda] = PublicKey.findProgramAddressSync(
+        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const seed = makeVrfSeed(vrfCounter.toNumber(), 1);
+      const [vrfRandomnessPda] = PublicKey.findProgramAddressSync(
+        [RANDOMNESS_ACCOUNT_SEED, seed],
+        ORAO_VRF_PROGRAM_ID
+      );
+      const [vrfConfigPda] = PublicKey.findProgramAddressSync(
+        [CONFIG_ACCOUNT_SEED],
+        ORAO_VRF_PROGRAM_ID
+      );
+
+      try {
+        await program.methods
+          .throwBall(0, 0) // Slot 0 has a Pokemon
+          .accounts({
+            player: noBallsPlayer.publicKey,
+            gameConfig: gameConfigPda,
+            pokemonSlots: pokemonSlotsPda,
+            playerInventory: noBallsInventory,
+            vrfRequest: vrfRequestPda,
+            vrfConfig: vrfConfigPda,
+            vrfRandomness: vrfRandomnessPda,
+            vrfTreasury: vrfConfigPda,
+            oraoVrf: ORAO_VRF_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([noBallsPlayer])
+          .rpc();
+        assert.fail("Should have failed — player has no inventory");
+      } catch (err) {
+        assert.ok(err);
+      }
+    });
+  });
+
+  // ============================================================
+  // CONSUME RANDOMNESS + CLAIM PRIZE
+  // Performs a real Master Ball throw on devnet so a VrfRequest exists,
+  // then tries to redirect its result. Winner and inventory checks run
+  // before fulfillment; the catch test needs ORAO to fulfill and the
+  // 99% Master Ball roll to catch. Claim tests use the fixture prize.
+  // ============================================================
+
+  describe("consume_randomness and claim_prize", () => {
+    const attacker = Keypair.generate();
+    let attackerInventoryPda: PublicKey;
+    let vrfRequestPda: PublicKey;
+    let vrfRandomnessPda: PublicKey;
+    let pendingPrizePda: PublicKey;
+
+    /** Build consume_randomness with overridable accounts. */
+    const consume = async (
+      overrides: { winner?: PublicKey; playerInventory?: PublicKey } = {}
+    ) => {
+      return program.methods
+        .consumeRandomness()
+        .accounts({
+          payer: attacker.publicKey,
+          gameConfig: gameConfigPda,
+          pokemonSlots: pokemonSlotsPda,
+          vrfRequest: vrfRequestPda,
+          requestPayer: playerKeypair.publicKey,
+          vrfRandomness: vrfRandomnessPda,
+          nftVault: nftVaultPda,
+          playerInventory: overrides.playerInventory ?? playerInventoryPda,
+          winner: overrides.winner ?? playerKeypair.publicKey,
+          pendingPrize: pendingPrizePda,
+          session: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([attacker])
+        .rpc();
+    };
+
+    /** Retry while ORAO has not fulfilled the request yet. */
+    const whenFulfilled = async (send: () => Promise<string>) => {
+      for (let attempt = 0; attempt < 30; attempt++) {
+        try {
+          return await send();
+        } catch (err) {
+          if (!err.toString().includes("VrfNotFulfilled")) throw err;
+          await new Promise((r) => setTimeout(r, 2_000));
+        }
+      }
+      throw new Error("ORAO did not fulfill the request in time");
+    };
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        attacker.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      // Give the attacker an inventory of their own
+      const attackerAta = await createAssociatedTokenAccount(
+        provider.connection,
+        (authority as any).payer,
+        solballsMint,
+        attacker.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        (authority as any).payer,
+        solballsMint,
+        attackerAta,
+        authority.publicKey,
+        1_000_000
+      );
+      [attackerInventoryPda] = PublicKey.findProgramAddressSync(
+        [PLAYER_INV_SEED, attacker.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .purchaseBalls(0, 1, ANY_COST)
+        .accounts({
+          player: attacker.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: attackerAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: attackerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([attacker])
+        .rpc();
+
+      // Player buys a Master Ball and throws it at slot 0
+      await program.methods
+        .purchaseBalls(3, 1, ANY_COST)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          playerTokenAccount: playerSolballsAta,
+          gameSolballsAccount: gameSolballsAta,
+          playerInventory: playerInventoryPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
+      [vrfRequestPda] = PublicKey.findProgramAddressSync(
+        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [pendingPrizePda] = PublicKey.findProgramAddressSync(
+        [PENDING_PRIZE_SEED, vrfRequestPda.toBuffer()],
+        program.programId
+      );
+      [vrfRandomnessPda] = PublicKey.findProgramAddressSync(
+        [RANDOMNESS_ACCOUNT_SEED, makeVrfSeed(vrfCounter.toNumber(), 1)],
+        ORAO_VRF_PROGRAM_ID
+      );
+      const [vrfConfigPda] = PublicKey.findProgramAddressSync(
+        [CONFIG_ACCOUNT_SEED],
+        ORAO_VRF_PROGRAM_ID
+      );
+
+      // NetworkState: 8-byte discriminator + 32-byte authority + 32-byte treasury
+      const networkState = await provider.connection.getAccountInfo(vrfConfigPda);
+      const vrfTreasury = new PublicKey(networkState.data.subarray(40, 72));
+
+      await program.methods
+        .throwBall(0, 3)
+        .accounts({
+          player: playerKeypair.publicKey,
+          gameConfig: gameConfigPda,
+          pokemonSlots: pokemonSlotsPda,
+          playerInventory: playerInventoryPda,
+          vrfRequest: vrfRequestPda,
+          vrfConfig: vrfConfigPda,
+          vrfRandomness: vrfRandomnessPda,
+          vrfTreasury,
+          oraoVrf: ORAO_VRF_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerKeypair])
+        .rpc();
+    });
+
+    it("rejects a winner that is not the thrower", async () => {
+      try {
+        await consume({ winner: attacker.publicKey });
+        assert.fail("Should have failed with WinnerMismatch");
+      } catch (err) {
+        assert.ok(err.toString().includes("WinnerMismatch"));
+      }
+    });
+
+    it("rejects another player's inventory", async () => {
+      try {
+        await consume({ playerInventory: attackerInventoryPda });
+        assert.fail("Should have failed with ConstraintSeeds");
+      } catch (err) {
+        assert.ok(err.toString().includes("ConstraintSeeds"));
+      }
+    });
+
+    it("records a claimable PendingPrize on catch", async function () {
+      const vaultBefore = await program.account.nftVault.fetch(nftVaultPda);
+      await whenFulfilled(() => consume());
+      assert.isNull(await provider.connection.getAccountInfo(vrfRequestPda));
+
+      // The 1% Master Ball miss creates no prize
+      if (!(await provider.connection.getAccountInfo(pendingPrizePda))) this.skip();
+
+      const prize = await program.account.pendingPrize.fetch(pendingPrizePda);
+      assert.ok(prize.winner.equals(playerKeypair.publicKey));
+      assert.ok(prize.rentPayer.equals(attacker.publicKey));
+      assert.ok(prize.vrfRequest.equals(vrfRequestPda));
+
+      // NFT left the vault list but is still held by the vault ATA
+      const vaultAfter = await program.account.nftVault.fetch(nftVaultPda);
+      assert.equal(vaultAfter.count, vaultBefore.count - 1);
+      const vaultAta = await getAssociatedTokenAddress(prize.nftMint, nftVaultPda, true);
+      assert.equal(Number((await getAccount(provider.connection, vaultAta)).amount), 1);
+    });
+
+    /** Build claim_prize for the fixture prize with overridable winner and destination. */
+    const claim = async (overrides: { winner?: PublicKey; winnerNftAccount?: PublicKey } = {}) => {
+      const prize = await program.account.pendingPrize.fetch(FIXTURE_PENDING_PRIZE);
+      const winner = overrides.winner ?? prize.winner;
+      return program.methods
+        .claimPrize()
+        .accounts({
+          payer: attacker.publicKey,
+          gameConfig: gameConfigPda,
+          nftVault: nftVaultPda,
+          pendingPrize: FIXTURE_PENDING_PRIZE,
+          rentPayer: prize.rentPayer,
+          winner,
+          nftMint: prize.nftMint,
+          vaultNftAccount: await getAssociatedTokenAddress(prize.nftMint, nftVaultPda, true),
+          winnerNftAccount:
+            overrides.winnerNftAccount ??
+            (await getAssociatedTokenAddress(prize.nftMint, winner)),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([attacker])
+        .rpc();
+    };
+
+    it("rejects a claim for someone other than the winner", async () => {
+      try {
+        await claim({ winner: attacker.publicKey });
+        assert.fail("Should have failed with WinnerMismatch");
+      } catch (err) {
+        assert.ok(err.toString().includes("WinnerMismatch"));
+      }
+    });
+
+    it("rejects a destination not owned by the winner", async () => {
+      const prize = await program.account.pendingPrize.fetch(FIXTURE_PENDING_PRIZE);
+      const attackerAta = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        (authority as any).payer,
+        prize.nftMint,
+        attacker.publicKey
+      );
+
+      try {
+        await claim({ winnerNftAccount: attackerAta.address });
+        assert.fail("Should have failed with ConstraintTokenOwner");
+      } catch (err) {
+        assert.ok(err.toString().includes("ConstraintTokenOwner"));
+      }
+    });
+
+    it("any cranker delivers the prize to the winner", async () => {
+      const prize = await program.account.pendingPrize.fetch(FIXTURE_PENDING_PRIZE);
+      await claim();
+
+      const winnerAta = await getAssociatedTokenAddress(prize.nftMint, prize.winner);
+      assert.equal(Number((await getAccount(provider.connection, winnerAta)).amount), 1);
+      assert.isNull(await provider.connection.getAccountInfo(FIXTURE_PENDING_PRIZE));
+    });
+  });
+
+  // ============================================================
+  // THROW BALL FOR (relayed, ed25519-signed intents)
+  // ============================================================
+
+  describe("throw_ball_for", () => {
+    const relayer = Keypair.generate();
+    const SLOT = 5;
+    let vrfConfigPda: PublicKey;
+    let vrfTreasury: PublicKey;
+
+    /** Accounts for the next relayed throw (derived from the current VRF counter). */
+    const relayAccounts = async (signer: PublicKey) => {
+      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
+      const [vrfRequest] = PublicKey.findProgramAddressSync(
+        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [vrfRandomness] = PublicKey.findProgramAddressSync(
+        [RANDOMNESS_ACCOUNT_SEED, makeVrfSeed(vrfCounter.toNumber(), 1)],
+        ORAO_VRF_PROGRAM_ID
+      );
+      return {
+        relayer: signer,
+        player: playerKeypair.publicKey,
+        gameConfig: gameConfigPda,
+        pokemonSlots: pokemonSlotsPda,
+        playerInventory: playerInventoryPda,
+        vrfRequest,
+        vrfConfig: vrfConfigPda,
+        vrfRandomness,
+        vrfTreasury,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        oraoVrf: ORAO_VRF_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+    };
+
+    /** ed25519 precompile instruction over the intent, signed by `signer`. */
+    const signIntent = (signer: Keypair, nonce: BN, expiry: BN, ballType = 0) =>
+      Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.secretKey,
+        message: makeRelayMessage(program.programId, SLOT, ballType, nonce, expiry),
+      });
+
+    const nextNonce = async () =>
+      (await program.account.playerInventory.fetch(playerInventoryPda)).relayNonce;
+
+    const inOneHour = () => new BN(Math.floor(Date.now() / 1000) + 3600);
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        relayer.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      [vrfConfigPda] = PublicKey.findProgramAddressSync(
+        [CONFIG_ACCOUNT_SEED],
+        ORAO_VRF_PROGRAM_ID
+      );
+      const networkState = await provider.connection.getAccountInfo(vrfConfigPda);
+      vrfTreasury = new PublicKey(networkState.data.subarray(40, 72));
+    });
+
+    it("sets the relayer", async () => {
+      await program.methods
+        .setRelayer(relayer.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(config.relayer.equals(relayer.publicKey));
+    });
+
+    it("rejects a signer that is not the relayer", async () => {
+      const nonce = await nextNonce();
+      const expiry = inOneHour();
+      try {
+        await program.methods
+          .throwBallFor(SLOT, 0, nonce, expiry)
+          .accounts(await relayAccounts(playerKeypair.publicKey))
+          .preInstructions([signIntent(playerKeypair, nonce, expiry)])
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InvalidRelayer");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRelayer"));
+      }
+    });
+
+    it("rejects a throw without the ed25519 instruction", async () => {
+      try {
+        await program.methods
+          .throwBallFor(SLOT, 0, await nextNonce(), inOneHour())
+          .accounts(await relayAccounts(relayer.publicKey))
+          .signers([relayer])
+          .rpc();
+        assert.fail("Should have failed with MissingEd25519Instruction");
+      } catch (err) {
+        assert.ok(err.toString().includes("MissingEd25519Instruction"));
+      }
+    });
+
+    it("rejects an intent signed by someone other than the player", async () => {
+      const nonce = await nextNonce();
+      const expiry = inOneHour();
+      try {
+        await program.methods
+          .throwBallFor(SLOT, 0, nonce, expiry)
+          .accounts(await relayAccounts(relayer.publicKey))
+          .preInstructions([signIntent(relayer, nonce, expiry)])
+          .signers([relayer])
+          .rpc();
+        assert.fail("Should have failed with InvalidRelaySignature");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRelaySignature"));
+      }
+    });
+
+    it("rejects an intent for a different ball type", async () => {
+      const nonce = await nextNonce();
+      const expiry = inOneHour();
+      try {
+        await program.methods
+          .throwBallFor(SLOT, 1, nonce, expiry)
+          .accounts(await relayAccounts(relayer.publicKey))
+          .preInstructions([signIntent(playerKeypair, nonce, expiry, 0)])
+          .signers([relayer])
+          .rpc();
+        assert.fail("Should have failed with InvalidRelaySignature");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRelaySignature"));
+      }
+    });
+
+    it("rejects an expired intent", async () => {
+      const nonce = await nextNonce();
+      const expiry = new BN(Math.floor(Date.now() / 1000) - 60);
+      try {
+        await program.methods
+          .throwBallFor(SLOT, 0, nonce, expiry)
+          .accounts(await relayAccounts(relayer.publicKey))
+          .preInstructions([signIntent(playerKeypair, nonce, expiry)])
+          .signers([relayer])
+          .rpc();
+        assert.fail("Should have failed with RelayIntentExpired");
+      } catch (err) {
+        assert.ok(err.toString().includes("RelayIntentExpired"));
+      }
+    });
+
+    it("relays a player-signed throw and consumes the nonce", async () => {
+      const nonce = await nextNonce();
+      const expiry = inOneHour();
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      const accounts = await relayAccounts(relayer.publicKey);
+
+      await program.methods
+        .throwBallFor(SLOT, 0, nonce, expiry)
+        .accounts(accounts)
+        .preInstructions([signIntent(playerKeypair, nonce, expiry)])
+        .signers([relayer])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] - 1);
+      assert.equal(after.relayNonce.toNumber(), nonce.toNumber() + 1);
+
+      const vrfRequest = await program.account.vrfRequest.fetch(accounts.vrfRequest);
+      assert.ok(vrfRequest.player.equals(playerKeypair.publicKey));
+      assert.ok(vrfRequest.payer.equals(relayer.publicKey));
+    });
+
+    it("rejects a replayed intent", async () => {
+      const usedNonce = (await nextNonce()).subn(1);
+      const expiry = inOneHour();
+      try {
+        await program.methods
+          .throwBallFor(SLOT, 0, usedNonce, expiry)
+          .accounts(await relayAccounts(relayer.publicKey))
+          .preInstructions([signIntent(playerKeypair, usedNonce, expiry)])
+          .signers([relayer])
+          .rpc();
+        assert.fail("Should have failed with InvalidRelayNonce");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidRelayNonce"));
+      }
+    });
+
+    it("disables relaying", async () => {
+      await program.methods
+        .setRelayer(PublicKey.default)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.ok(config.relayer.equals(PublicKey.default));
+    });
+  });
+
+  // ============================================================
+  // SESSION KEYS
+  // ============================================================
+
+  describe("session keys", () => {
+    const sessionSigner = Keypair.generate();
+    const SLOT = 5;
+    const SESSION_ALLOW_THROW = 1 << 0;
+    let sessionPda: PublicKey;
+    let vrfConfigPda: PublicKey;
+    let vrfTreasury: PublicKey;
+
+    const findSession = (key: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [SESSION_SEED, playerKeypair.publicKey.toBuffer(), key.toBuffer()],
+        program.programId
+      )[0];
+
+    /** Accounts for the next session throw (derived from the current VRF counter). */
+    const sessionThrowAccounts = async (signer: PublicKey, session = sessionPda) => {
+      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
+      const [vrfRequest] = PublicKey.findProgramAddressSync(
+        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [vrfRandomness] = PublicKey.findProgramAddressSync(
+        [RANDOMNESS_ACCOUNT_SEED, makeVrfSeed(vrfCounter.toNumber(), 1)],
+        ORAO_VRF_PROGRAM_ID
+      );
+      return {
+        sessionSigner: signer,
+        player: playerKeypair.publicKey,
+        session,
+        gameConfig: gameConfigPda,
+        pokemonSlots: pokemonSlotsPda,
+        playerInventory: playerInventoryPda,
+        vrfRequest,
+        vrfConfig: vrfConfigPda,
+        vrfRandomness,
+        vrfTreasury,
+        oraoVrf: ORAO_VRF_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+    };
+
+    const inOneHour = () => new BN(Math.floor(Date.now() / 1000) + 3600);
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        sessionSigner.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      sessionPda = findSession(sessionSigner.publicKey);
+      [vrfConfigPda] = PublicKey.findProgramAddressSync(
+        [CONFIG_ACCOUNT_SEED],
+        ORAO_VRF_PROGRAM_ID
+      );
+      const networkState = await provider.connection.getAccountInfo(vrfConfigPda);
+      vrfTreasury = new PublicKey(networkState.data.subarray(40, 72));
+    });
+
+    it("rejects a session expiring beyond the maximum duration", async () => {
+      const expiry = new BN(Math.floor(Date.now() / 1000) + 8 * 24 * 60 * 60);
+      try {
+        await program.methods
+          .createSession(sessionSigner.publicKey, expiry, [1, 0, 0, 0], SESSION_ALLOW_THROW)
+          .accounts({
+            player: playerKeypair.publicKey,
+            session: sessionPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InvalidSessionExpiry");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidSessionExpiry"));
+      }
+    });
+
+    it("rejects unknown session permissions", async () => {
+      try {
+        await program.methods
+          .createSession(sessionSigner.publicKey, inOneHour(), [1, 0, 0, 0], 0x80)
+          .accounts({
+            player: playerKeypair.publicKey,
+            session: sessionPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed with InvalidSessionPermissions");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidSessionPermissions"));
+      }
+    });
+
+    it("creates a session with one Poke Ball allowance", async () => {
+      const expiry = inOneHour();
+      await program.methods
+        .createSession(sessionSigner.publicKey, expiry, [1, 0, 0, 0], SESSION_ALLOW_THROW)
+        .accounts({
+          player: playerKeypair.publicKey,
+          session: sessionPda,
+          systemProgram: SystemProgram.programId,
         })
+        .signers([playerKeypair])
         .rpc();
 
-      // Verify treasury tracking
-      const treasuryConfig = await program.account.treasuryConfig.fetch(treasuryConfigPda);
-      assert.equal(treasuryConfig.totalWithdrawn.toNumber(), withdrawAmount.toNumber());
+      const session = await program.account.sessionKey.fetch(sessionPda);
+      assert.ok(session.owner.equals(playerKeypair.publicKey));
+      assert.ok(session.sessionKey.equals(sessionSigner.publicKey));
+      assert.equal(session.expiresAt.toNumber(), expiry.toNumber());
+      assert.deepEqual(session.ballAllowance, [1, 0, 0, 0]);
+      assert.equal(session.allowedMask, SESSION_ALLOW_THROW);
     });
 
-    it("fails with zero withdrawal", async () => {
-      const authoritySolballsAta = await getAssociatedTokenAddress(
-        solballsMint,
-        authority.publicKey
+    it("rejects a ball type outside the session allowance", async () => {
+      try {
+        await program.methods
+          .throwBallWithSession(SLOT, 1)
+          .accounts(await sessionThrowAccounts(sessionSigner.publicKey))
+          .signers([sessionSigner])
+          .rpc();
+        assert.fail("Should have failed with SessionAllowanceExceeded");
+      } catch (err) {
+        assert.ok(err.toString().includes("SessionAllowanceExceeded"));
+      }
+    });
+
+    it("throws from the owner's inventory and debits the allowance", async () => {
+      const before = await program.account.playerInventory.fetch(playerInventoryPda);
+      const accounts = await sessionThrowAccounts(sessionSigner.publicKey);
+
+      await program.methods
+        .throwBallWithSession(SLOT, 0)
+        .accounts(accounts)
+        .signers([sessionSigner])
+        .rpc();
+
+      const after = await program.account.playerInventory.fetch(playerInventoryPda);
+      assert.equal(after.balls[0], before.balls[0] - 1);
+
+      const session = await program.account.sessionKey.fetch(sessionPda);
+      assert.deepEqual(session.ballAllowance, [0, 0, 0, 0]);
+
+      const vrfRequest = await program.account.vrfRequest.fetch(accounts.vrfRequest);
+      assert.ok(vrfRequest.player.equals(playerKeypair.publicKey));
+      assert.ok(vrfRequest.payer.equals(sessionSigner.publicKey));
+      // Recorded so a voided or cancelled throw can restore the allowance
+      assert.ok(vrfRequest.session.equals(sessionPda));
+    });
+
+    it("rejects a throw once the allowance is used up", async () => {
+      try {
+        await program.methods
+          .throwBallWithSession(SLOT, 0)
+          .accounts(await sessionThrowAccounts(sessionSigner.publicKey))
+          .signers([sessionSigner])
+          .rpc();
+        assert.fail("Should have failed with SessionAllowanceExceeded");
+      } catch (err) {
+        assert.ok(err.toString().includes("SessionAllowanceExceeded"));
+      }
+    });
+
+    it("rejects a signer that is not the session key", async () => {
+      const impostor = Keypair.generate();
+      const airdropSig = await provider.connection.requestAirdrop(
+        impostor.publicKey,
+        LAMPORTS_PER_SOL
       );
+      await provider.connection.confirmTransaction(airdropSig);
 
       try {
         await program.methods
-          .withdrawRevenue(new BN(0))
+          .throwBallWithSession(SLOT, 0)
+          .accounts(await sessionThrowAccounts(impostor.publicKey))
+          .signers([impostor])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err) {
+        assert.ok(err.toString().includes("ConstraintSeeds"));
+      }
+    });
+
+    it("revokes the session", async () => {
+      await program.methods
+        .revokeSession()
+        .accounts({
+          player: playerKeypair.publicKey,
+          session: sessionPda,
+        })
+        .signers([playerKeypair])
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(sessionPda);
+      assert.isNull(info);
+    });
+  });
+
+  // ============================================================
+  // CLOSE FULFILLED REQUESTS
+  // ============================================================
+
+  describe("close_fulfilled_requests", () => {
+    it("fails with no requests to close", async () => {
+      try {
+        await program.methods
+          .closeFulfilledRequests()
           .accounts({
-            authority: authority.publicKey,
-            gameConfig: gameConfigPda,
-            treasuryConfig: treasuryConfigPda,
-            gameSolballsAccount: gameSolballsAta,
-            authoritySolballsAccount: authoritySolballsAta,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            cranker: playerKeypair.publicKey,
           })
+          .signers([playerKeypair])
           .rpc();
-        assert.fail("Should have failed with zero amount");
+        assert.fail("Should have failed with empty remaining_accounts");
       } catch (err) {
-        assert.ok(err.toString().includes("InsufficientWithdrawalAmount"));
+        assert.ok(err.toString().includes("InvalidRemainingAccounts"));
       }
     });
 
-    it("fails for non-authority", async () => {
-      const playerSolballsAta2 = await getAssociatedTokenAddress(
-        solballsMint,
-        playerKeypair.publicKey
-      );
-
+    it("rejects accounts that are not VrfRequests", async () => {
       try {
         await program.methods
-          .withdrawRevenue(new BN(1))
+          .closeFulfilledRequests()
           .accounts({
-            authority: playerKeypair.publicKey,
-            gameConfig: gameConfigPda,
-            treasuryConfig: treasuryConfigPda,
-            gameSolballsAccount: gameSolballsAta,
-            authoritySolballsAccount: playerSolballsAta2,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            cranker: playerKeypair.publicKey,
           })
+          .remainingAccounts([
+            { pubkey: gameConfigPda, isWritable: true, isSigner: false },
+            { pubkey: playerKeypair.publicKey, isWritable: true, isSigner: false },
+          ])
           .signers([playerKeypair])
           .rpc();
-        assert.fail("Should have failed for non-authority");
+        assert.fail("Should have failed on non-VrfRequest account");
       } catch (err) {
-        assert.ok(err);
+        assert.ok(err.toString().includes("InvalidVrfRequestAccount"));
       }
     });
   });
 
   // ============================================================
-  // THROW BALL (VRF Integration)
-  // Note: These tests require ORAO VRF devnet to be available.
-  // They validate the instruction structure but VRF fulfillment
-  // depends on the ORAO network being live on devnet.
+  // CANCEL STALE REQUEST
   // ============================================================
 
-  describe("throw_ball (VRF)", () => {
-    it("validates slot is active before throw", async () => {
-      // Slot 2 has no Pokemon
-      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
-
-      // Derive VRF request PDA
-      const [vrfRequestPda] = PublicKey.findProgramAddressSync(
-        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+  describe("cancel_stale_request", () => {
+    it("sets the VRF timeout", async () => {
+      await program.methods
+        .setVrfTimeout(new BN(600))
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
 
-      // Derive VRF seed to compute randomness PDA
-      const seed = makeVrfSeed(vrfCounter.toNumber(), 1); // VRF_TYPE_THROW
-      const [vrfRandomnessPda] = PublicKey.findProgramAddressSync(
-        [RANDOMNESS_ACCOUNT_SEED, seed],
-        ORAO_VRF_PROGRAM_ID
-      );
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(config.vrfTimeout.toNumber(), 600);
+    });
 
-      // Derive ORAO VRF config PDA
-      const [vrfConfigPda] = PublicKey.findProgramAddressSync(
-        [CONFIG_ACCOUNT_SEED],
-        ORAO_VRF_PROGRAM_ID
-      );
+    it("fails with a timeout below the minimum", async () => {
+      try {
+        await program.methods
+          .setVrfTimeout(new BN(10))
+          .accounts({
+            authority: authority.publicKey,
+            gameConfig: gameConfigPda,
+          })
+          .rpc();
+        assert.fail("Should have failed with InvalidVrfTimeout");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidVrfTimeout"));
+      }
+    });
 
+    it("fails to set the timeout for non-admin", async () => {
       try {
         await program.methods
-          .throwBall(2, 0) // Slot 2 is empty
+          .setVrfTimeout(new BN(600))
           .accounts({
-            player: playerKeypair.publicKey,
+            authority: playerKeypair.publicKey,
             gameConfig: gameConfigPda,
-            pokemonSlots: pokemonSlotsPda,
-            playerInventory: playerInventoryPda,
-            vrfRequest: vrfRequestPda,
-            vrfConfig: vrfConfigPda,
-            vrfRandomness: vrfRandomnessPda,
-            vrfTreasury: vrfConfigPda, // Placeholder — real test needs ORAO treasury
-            oraoVrf: ORAO_VRF_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
           })
           .signers([playerKeypair])
           .rpc();
-        assert.fail("Should have failed on inactive slot");
+        assert.fail("Should have failed with Unauthorized");
       } catch (err) {
-        assert.ok(
-          err.toString().includes("SlotNotActive") ||
-          err.toString().includes("Error") // VRF CPI may fail on devnet if not set up
-        );
+        assert.ok(err.toString().includes("Unauthorized"));
       }
     });
 
-    it("validates player has balls before throw", async () => {
-      // Create a new player with no balls
-      const noBallsPlayer = Keypair.generate();
-      const airdropSig = await provider.connection.requestAirdrop(
-        noBallsPlayer.publicKey,
-        2 * LAMPORTS_PER_SOL
-      );
-      await provider.connection.confirmTransaction(airdropSig);
-
-      // Create inventory PDA (won't have any balls)
-      const [noBallsInventory] = PublicKey.findProgramAddressSync(
-        [PLAYER_INV_SEED, noBallsPlayer.publicKey.toBuffer()],
-        program.programId
-      );
-
-      // This will fail because the player has no inventory (account doesn't exist yet)
-      // and the throw instruction doesn't use init_if_needed for inventory
-      const vrfCounter = (await program.account.gameConfig.fetch(gameConfigPda)).vrfCounter;
-      const [vrfRequestPda] = PublicKey.findProgramAddressSync(
-        [VRF_REQ_SEED, vrfCounter.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-      const seed = makeVrfSeed(vrfCounter.toNumber(), 1);
+    it("rejects accounts that are not VrfRequests", async () => {
       const [vrfRandomnessPda] = PublicKey.findProgramAddressSync(
-        [RANDOMNESS_ACCOUNT_SEED, seed],
-        ORAO_VRF_PROGRAM_ID
-      );
-      const [vrfConfigPda] = PublicKey.findProgramAddressSync(
-        [CONFIG_ACCOUNT_SEED],
+        [RANDOMNESS_ACCOUNT_SEED, makeVrfSeed(0, 1)],
         ORAO_VRF_PROGRAM_ID
       );
 
       try {
         await program.methods
-          .throwBall(0, 0) // Slot 0 has a Pokemon
+          .cancelStaleRequest()
           .accounts({
-            player: noBallsPlayer.publicKey,
+            caller: playerKeypair.publicKey,
             gameConfig: gameConfigPda,
             pokemonSlots: pokemonSlotsPda,
-            playerInventory: noBallsInventory,
-            vrfRequest: vrfRequestPda,
-            vrfConfig: vrfConfigPda,
+            vrfRequest: gameConfigPda,
+            requestPayer: authority.publicKey,
             vrfRandomness: vrfRandomnessPda,
-            vrfTreasury: vrfConfigPda,
-            oraoVrf: ORAO_VRF_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
+            playerInventory: playerInventoryPda,
+            session: null,
           })
-          .signers([noBallsPlayer])
+          .signers([playerKeypair])
           .rpc();
-        assert.fail("Should have failed — player has no inventory");
+        assert.fail("Should have failed on non-VrfRequest account");
       } catch (err) {
         assert.ok(err);
       }
     });
+
+    it("resets the VRF timeout to default", async () => {
+      await program.methods
+        .setVrfTimeout(new BN(300))
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+
+      const config = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(config.vrfTimeout.toNumber(), 300);
+    });
   });
 
   // ============================================================
@@ -1183,12 +4446,130 @@ describe("pokeball_game", () => {
       console.log(`  VRF counter: ${gameConfig.vrfCounter.toNumber()}`);
     });
   });
+
+  // ============================================================
+  // CONFIG CHANGE TIMELOCK
+  // Runs last: reducing the delay keeps the old one in force for an
+  // hour, which would block every later queued change.
+  // ============================================================
+
+  describe("config change timelock", () => {
+    const [pendingConfigPda] = PublicKey.findProgramAddressSync(
+      [PENDING_CONFIG_SEED],
+      program.programId
+    );
+
+    const setDelay = async (delay: number) => {
+      await program.methods
+        .setConfigChangeDelay(new BN(delay))
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+        })
+        .rpc();
+    };
+
+    it("rejects execution before the eta and can be cancelled", async () => {
+      await setDelay(3600);
+
+      const values = await currentValues();
+      values.catchRates[0] = 50;
+      await program.methods
+        .queueConfigChange(values)
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const pending = await program.account.pendingConfigChange.fetch(pendingConfigPda);
+      assert.equal(pending.eta.toNumber() - pending.queuedAt.toNumber(), 3600);
+
+      try {
+        await program.methods
+          .executeConfigChange()
+          .accounts({
+            executor: playerKeypair.publicKey,
+            gameConfig: gameConfigPda,
+            pendingConfigChange: pendingConfigPda,
+            proposer: authority.publicKey,
+          })
+          .signers([playerKeypair])
+          .rpc();
+        assert.fail("Should have failed before eta");
+      } catch (err) {
+        assert.ok(err.toString().includes("ConfigChangeNotReady"));
+      }
+
+      await program.methods
+        .cancelConfigChange()
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+
+      let gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.catchRates[0], catchRate); // unchanged
+
+      // Reductions only take effect once the old delay has elapsed
+      await setDelay(0);
+      gameConfig = await program.account.gameConfig.fetch(gameConfigPda);
+      assert.equal(gameConfig.configChangeDelay.toNumber(), 0);
+      assert.equal(gameConfig.configChangeDelayFloor.toNumber(), 3600);
+
+      await program.methods
+        .queueConfigChange(await currentValues())
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      const floored = await program.account.pendingConfigChange.fetch(pendingConfigPda);
+      assert.equal(floored.eta.toNumber() - floored.queuedAt.toNumber(), 3600);
+
+      await program.methods
+        .cancelConfigChange()
+        .accounts({
+          authority: authority.publicKey,
+          gameConfig: gameConfigPda,
+          pendingConfigChange: pendingConfigPda,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+    });
+  });
 });
 
 // ============================================================
 // HELPER FUNCTIONS
 // ============================================================
 
+/**
+ * Mirrors the on-chain relay_message function.
+ * program_id (32) || slot_index (1) || ball_type (1) || nonce (8, LE) || expiry (8, LE)
+ */
+function makeRelayMessage(
+  programId: PublicKey,
+  slotIndex: number,
+  ballType: number,
+  nonce: BN,
+  expiry: BN
+): Buffer {
+  return Buffer.concat([
+    programId.toBuffer(),
+    Buffer.from([slotIndex, ballType]),
+    nonce.toArrayLike(Buffer, "le", 8),
+    expiry.toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
 /**
  * Mirrors the on-chain make_vrf_seed function.
  * Produces a 32-byte seed from the VRF counter and request type.